@@ -0,0 +1,267 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Dir(inside the project root) where check results are kept between runs
+pub const CACHE_DIR: &str = ".checkmark-cache";
+
+/// Entries not used for that long are dropped on save, so entries of deleted files don't pile up
+const MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// Issues found in a single file, along with the key they were found for
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Entry {
+    key: String,
+    issues: Vec<common::CheckIssue>,
+    /// Seconds since the Unix epoch when the entry was last used
+    #[serde(default)]
+    used_at: u64,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// Dir of the cache for the project root. Roots that are not local dirs or files(stdin, remote repos
+/// and archives) keep the cache in the current dir
+pub fn dir(project_root: &str) -> PathBuf {
+    let root = Path::new(project_root);
+    if checkmark_ls::display_root(project_root).is_some() {
+        return PathBuf::from(CACHE_DIR);
+    }
+    match root.parent() {
+        _ if root.is_dir() => root.join(CACHE_DIR),
+        Some(parent) if root.is_file() => parent.join(CACHE_DIR),
+        _ => PathBuf::from(CACHE_DIR),
+    }
+}
+
+/// Persistent cache of issues found by a single tool, one entry per file.
+/// Entry is reused while file content, checkmark version and relevant config stay the same
+pub struct Cache {
+    /// Where entries are stored, no file means cache is disabled
+    file: Option<PathBuf>,
+    tool: String,
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl Cache {
+    /// Load previously cached entries of the tool from the dir
+    pub fn open(dir: &Path, tool: &str) -> Self {
+        let file = dir.join(format!("{tool}.json"));
+        let entries = match std::fs::read_to_string(&file) {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_else(|err| {
+                log::warn!("Ignoring corrupted cache {:#?}: {}", &file, err);
+                HashMap::new()
+            }),
+            Err(_) => HashMap::new(),
+        };
+        Self {
+            file: Some(file),
+            tool: tool.to_string(),
+            entries: Mutex::new(entries),
+        }
+    }
+
+    /// Cache that never has any entry and is never saved
+    pub fn disabled() -> Self {
+        Self {
+            file: None,
+            tool: String::new(),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Hash of everything that affects check result of the file.
+    /// Settings are the parts of the config used by the tool, hashed as JSON(object keys are sorted)
+    fn key(&self, file: &common::MarkDownFile, settings: &impl serde::Serialize) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(self.tool.as_bytes());
+        hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+        let settings = serde_json::to_value(settings).unwrap_or_else(|err| {
+            log::warn!("Unable to serialize {} settings: {}", &self.tool, err);
+            serde_json::Value::Null
+        });
+        hasher.update(settings.to_string().as_bytes());
+        hasher.update(file.content.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Issues found in the file last time, unless anything has changed since then
+    pub fn get(
+        &self,
+        file: &common::MarkDownFile,
+        settings: &impl serde::Serialize,
+    ) -> Option<Vec<common::CheckIssue>> {
+        self.file.as_ref()?;
+        let key = self.key(file, settings);
+        match self.entries.lock().unwrap().get_mut(&file.path) {
+            Some(entry) if entry.key.eq(&key) => {
+                log::debug!("Using cached {} result of {:#?}", &self.tool, &file.path);
+                entry.used_at = now();
+                Some(entry.issues.clone())
+            }
+            _ => None,
+        }
+    }
+
+    pub fn put(
+        &self,
+        file: &common::MarkDownFile,
+        settings: &impl serde::Serialize,
+        issues: &[common::CheckIssue],
+    ) {
+        if self.file.is_some() {
+            let entry = Entry {
+                key: self.key(file, settings),
+                issues: issues.to_vec(),
+                used_at: now(),
+            };
+            self.entries
+                .lock()
+                .unwrap()
+                .insert(file.path.clone(), entry);
+        }
+    }
+
+    /// Write entries back to the disk, except ones not used for longer than [`MAX_AGE`].
+    /// Entries of files not checked during the current run are kept, so checking a single file
+    /// doesn't invalidate the rest of the project.
+    /// Cache dir is ignored by git, so it doesn't get committed by accident
+    pub fn save(&self) {
+        if let Some(file) = &self.file {
+            let oldest = now().saturating_sub(MAX_AGE.as_secs());
+            self.entries
+                .lock()
+                .unwrap()
+                .retain(|_, entry| entry.used_at >= oldest);
+            let write = || -> std::io::Result<()> {
+                let dir = file.parent().unwrap_or(Path::new("."));
+                std::fs::create_dir_all(dir)?;
+                std::fs::write(dir.join(".gitignore"), "*\n")?;
+                let content = serde_json::to_string(&*self.entries.lock().unwrap())?;
+                std::fs::write(file, content)
+            };
+            if let Err(err) = write() {
+                log::warn!("Unable to save cache {:#?}: {}", &file, err);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(content: &str) -> common::MarkDownFile {
+        common::MarkDownFile {
+            path: "README.md".to_string(),
+            content: content.to_string(),
+            issues: vec![],
+        }
+    }
+
+    fn issue() -> common::CheckIssue {
+        common::CheckIssueBuilder::default()
+            .set_category(common::IssueCategory::Linting)
+            .set_severity(common::IssueSeverity::Warning)
+            .set_file_path("README.md".to_string())
+            .set_row_num_start(1)
+            .set_row_num_end(1)
+            .set_col_num_start(1)
+            .set_col_num_end(1)
+            .set_offset_start(0)
+            .set_offset_end(1)
+            .set_message("Issue".to_string())
+            .build()
+    }
+
+    #[test]
+    fn entries_are_reused_until_anything_changes() {
+        let dir = std::env::temp_dir().join("checkmark_cache_tests");
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+        let cache = Cache::open(&dir, "lint");
+        assert_eq!(cache.get(&file("# Heading"), &"settings"), None);
+        cache.put(&file("# Heading"), &"settings", &[issue()]);
+        cache.save();
+
+        let cache = Cache::open(&dir, "lint");
+        assert_eq!(
+            cache.get(&file("# Heading"), &"settings"),
+            Some(vec![issue()])
+        );
+        assert_eq!(cache.get(&file("# Changed"), &"settings"), None);
+        assert_eq!(cache.get(&file("# Heading"), &"changed"), None);
+        assert_eq!(
+            Cache::open(&dir, "fmt").get(&file("# Heading"), &"settings"),
+            None
+        );
+        assert!(dir.join(".gitignore").exists());
+    }
+
+    #[test]
+    fn only_entries_not_used_for_long_are_pruned() {
+        let dir = std::env::temp_dir().join("checkmark_cache_prune_tests");
+        if dir.exists() {
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+        let other_file = common::MarkDownFile {
+            path: "OTHER.md".to_string(),
+            ..file("# Other")
+        };
+        let old_file = common::MarkDownFile {
+            path: "OLD.md".to_string(),
+            ..file("# Old")
+        };
+        let cache = Cache::open(&dir, "lint");
+        cache.put(&file("# Heading"), &"settings", &[issue()]);
+        cache.put(&other_file, &"settings", &[]);
+        cache.put(&old_file, &"settings", &[]);
+        cache
+            .entries
+            .lock()
+            .unwrap()
+            .get_mut("OLD.md")
+            .unwrap()
+            .used_at = now() - MAX_AGE.as_secs() - 1;
+        cache.save();
+
+        let cache = Cache::open(&dir, "lint");
+        assert_eq!(
+            cache.get(&file("# Heading"), &"settings"),
+            Some(vec![issue()])
+        );
+        cache.save();
+
+        let cache = Cache::open(&dir, "lint");
+        assert_eq!(cache.get(&other_file, &"settings"), Some(vec![]));
+        assert_eq!(cache.get(&old_file, &"settings"), None);
+    }
+
+    #[test]
+    fn cache_dir_is_inside_the_project_root() {
+        let root = std::env::temp_dir().join("checkmark_cache_dir_tests");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("README.md"), "# Readme").unwrap();
+        assert_eq!(dir(root.to_str().unwrap()), root.join(CACHE_DIR));
+        assert_eq!(
+            dir(root.join("README.md").to_str().unwrap()),
+            root.join(CACHE_DIR)
+        );
+        assert_eq!(dir("-"), PathBuf::from(CACHE_DIR));
+    }
+
+    #[test]
+    fn disabled_cache_is_always_empty() {
+        let cache = Cache::disabled();
+        cache.put(&file("# Heading"), &"settings", &[issue()]);
+        assert_eq!(cache.get(&file("# Heading"), &"settings"), None);
+    }
+}
@@ -26,6 +26,9 @@ pub struct FmtCommand {
     /// Display a detailed comparison if formatting issues are detected
     #[arg(long, action, requires = "check")]
     pub show_diff: bool,
+    /// Verify mode: Fuzzes the formatter with the files and their variants without writing anything. Reports formatter bugs, such as changed meaning of a document
+    #[arg(long, action, conflicts_with = "check")]
+    pub verify: bool,
 }
 
 #[derive(Debug, clap::Parser)]
@@ -111,6 +114,22 @@ pub struct SpellcheckCommand {
     pub words_whitelist: Vec<String>,
 }
 
+#[derive(Debug, clap::Parser)]
+#[command(long_about = None)]
+pub struct FrontMatterCommand {
+    /// Path to the JSON Schema that front matter of documents must match
+    #[arg(long, value_hint=clap::ValueHint::FilePath)]
+    pub schema: Option<String>,
+}
+
+#[derive(Debug, clap::Parser)]
+#[command(long_about = None)]
+pub struct StaleCommand {
+    /// Documents reviewed(or, without a review date in front matter, changed in git) longer ago than that are reported. Defaults to 365
+    #[arg(long)]
+    pub max_age_days: Option<u32>,
+}
+
 #[derive(Debug, clap::Parser)]
 #[command(long_about = None)]
 pub struct GenerateConfigCommand {
@@ -119,6 +138,49 @@ pub struct GenerateConfigCommand {
     pub path: String,
 }
 
+#[derive(Debug, clap::Parser)]
+#[command(long_about = None)]
+pub struct InitCommand {
+    /// Overwrite the config file when it already exists
+    #[arg(long, default_value = "false")]
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, clap::ValueEnum)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+#[derive(Debug, clap::Parser)]
+#[command(long_about = None)]
+pub struct ConfigShowCommand {
+    /// Output format
+    #[arg(long, value_enum, default_value = "toml")]
+    pub format: ConfigFormat,
+}
+
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigSubcommands {
+    /// Validates config files and exits with non-zero code when any of them has unknown keys or invalid values
+    Validate,
+    /// Prints JSON Schema of the config file, i.e. for completion in editors
+    Schema,
+    /// Prints effective config after merging defaults, config files and CLI options, with the origin of every value
+    Show(ConfigShowCommand),
+}
+
+#[derive(Debug, clap::Parser)]
+#[command(long_about = None)]
+pub struct ConfigCommand {
+    #[command(subcommand)]
+    pub subcommands: ConfigSubcommands,
+}
+
+#[derive(Debug, clap::Parser)]
+#[command(long_about = None)]
+pub struct LspCommand {}
+
 #[derive(Debug, clap::Subcommand)]
 pub enum Subcommands {
     /// Formats Markdown files. This will fix common formatting issues such as trailing whitespace, inconsistent line endings, and more
@@ -135,8 +197,18 @@ pub enum Subcommands {
     Compose(ComposeCommand),
     /// Checks the document for spelling errors(offline)
     Spellcheck(SpellcheckCommand),
+    /// Validates front matter of the documents against the JSON Schema, i.e. requires "title" and "owner" keys
+    FrontMatter(FrontMatterCommand),
+    /// Reports documents due for review by "last_reviewed"/"updated" date in front matter or by the last git commit
+    Stale(StaleCommand),
     /// Generates default configuration file
     GenerateConfig(GenerateConfigCommand),
+    /// Generates configuration file with the style inferred from existing documents
+    Init(InitCommand),
+    /// Validates configuration files or prints their JSON Schema
+    Config(ConfigCommand),
+    /// Starts Language Server Protocol server(stdio) for live diagnostics and formatting in editors
+    Lsp(LspCommand),
 }
 
 #[derive(Debug, clap::Parser)]
@@ -144,13 +216,22 @@ pub enum Subcommands {
 #[command(propagate_version = true)]
 pub struct Cli {
     /// Sets the project root, file, or web URL for scanning Markdown files.
-    /// Can also accept a Git repository.
+    /// Can also accept a Git repository, or "-" to read a single document from stdin.
     /// Defaults to the current directory if not specified
     #[arg(global = true, value_hint=clap::ValueHint::AnyPath, default_value=".")]
     pub project_root: String,
+    /// File name to report the document read from stdin(when project root is "-") with
+    #[arg(global = true, long, required = false, value_name = "FILE_PATH", value_hint=clap::ValueHint::FilePath)]
+    pub stdin_filename: Option<String>,
+    /// List of file patterns(globs, relative to the project root) to discover as Markdown files
+    #[arg(global = true, long, required = false, num_args = 1.., value_delimiter = ' ', value_hint=clap::ValueHint::AnyPath)]
+    pub include: Vec<String>,
     /// List of files(wildcards) to exclude from scanning
     #[arg(global = true, long, required = false, num_args = 1.., value_delimiter = ' ', value_hint=clap::ValueHint::AnyPath)]
     pub exclude: Vec<String>,
+    /// Do not skip files ignored by .gitignore, .ignore and .checkmarkignore files
+    #[arg(global = true, long, required = false, action)]
+    pub no_ignore: bool,
     /// Style: Type of heading style to enforce. Possible values are: "atx", "setext" or "consistent"
     #[arg(global = true, long, required = false)]
     pub style_headings: Option<String>,
@@ -166,6 +247,9 @@ pub struct Cli {
     /// Style: Default code block language to use. Defaults to "text"
     #[arg(global = true, long, required = false)]
     pub style_default_code_block_language: Option<String>,
+    /// Style: Type of hard line break style to enforce. Possible values are: "backslash", "spaces" or "consistent"
+    #[arg(global = true, long, required = false)]
+    pub style_hard_break: Option<String>,
     /// Sets the configuration file path. Overrides default files if set
     #[arg(global = true, long, short, action, required = false, value_name = "FILE_PATH", value_hint=clap::ValueHint::FilePath)]
     pub config: Option<String>,
@@ -175,6 +259,27 @@ pub struct Cli {
     /// Saves the report in SARIF format to a given file or defaults to './report.sarif' if no file is specified
     #[arg(global = true, long, action, required = false, value_name = "FILE_PATH", value_hint=clap::ValueHint::FilePath, default_missing_value="./report.sarif", num_args=0..=1)]
     pub sarif: Option<String>,
+    /// Git: Check only files added or modified since the given ref(commit, branch or tag), including uncommitted changes
+    #[arg(
+        global = true,
+        long,
+        required = false,
+        value_name = "REF",
+        conflicts_with = "staged"
+    )]
+    pub changed_since: Option<String>,
+    /// Git: Check only files staged for the next commit
+    #[arg(global = true, long, required = false, action)]
+    pub staged: bool,
+    /// Git: Report only issues on lines added or modified since the given ref(commit, branch or tag). Implies checking only changed files
+    #[arg(global = true, long, required = false, value_name = "REF")]
+    pub diff_lines: Option<String>,
+    /// Cache: Do not reuse results of previous runs for unchanged files and do not save them
+    #[arg(global = true, long, required = false, action)]
+    pub no_cache: bool,
+    /// Watch mode: After the first run, re-runs the command on changed Markdown files. Changing the config file re-runs it on all files
+    #[arg(global = true, long, required = false, action)]
+    pub watch: bool,
     /// Verbose logging: Provides detailed tool activity, useful for debugging
     #[arg(global = true, long, required = false, action)]
     pub verbose: bool,
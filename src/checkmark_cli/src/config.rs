@@ -1,24 +1,30 @@
-/// First, create one with default values
-/// Then, try reading from TOML file by path provided in CLI
-/// if no CLI option provided - try reading from default locations(replace when found)
-/// and then apply config from CLI because it has higher priority
-pub fn read_config(cli: &crate::cli::Cli) -> common::Config {
-    log::debug!("Building default config...");
+use crate::errors::AppError;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
-    let mut config = common::Config::default();
-    log::debug!("Default config built: {:#?}", &config);
+/// Config file read with all configs it extends merged beneath it
+#[derive(Clone)]
+pub struct ConfigFile {
+    /// Absolute path of the file
+    path: String,
+    table: toml::Table,
+    /// Config file that set every value of the table, by dotted key, i.e. "style.headings"
+    origins: HashMap<String, String>,
+}
 
-    log::debug!("Trying to read config from file...");
+/// Read the root config file: by path provided in CLI,
+/// or if no CLI option provided - from default locations(first found wins).
+/// Extended configs could be fetched from remote repos, so it is read once and then passed around
+pub fn read_root_table(cli: &crate::cli::Cli) -> Result<Option<ConfigFile>, AppError> {
     if let Some(cfg_path_from_cli) = &cli.config {
         log::debug!(
             "Trying to read config from CLI arg {}...",
             &cfg_path_from_cli
         );
-        if let Some(cfg) = common::Config::from_file(cfg_path_from_cli) {
-            config = cfg; // Replace default config with config from file
-        } else {
-            log::warn!("Config file not found in {}", &cfg_path_from_cli);
+        if let Some(file) = read_table(cfg_path_from_cli)? {
+            return Ok(Some(file));
         }
+        log::warn!("Config file not found in {}", &cfg_path_from_cli);
     } else {
         let default_locations = [
             "checkmark.toml",
@@ -37,20 +43,705 @@ pub fn read_config(cli: &crate::cli::Cli) -> common::Config {
             &default_locations
         );
         for file_path in default_locations.iter() {
-            if let Some(cfg) = common::Config::from_file(file_path) {
-                config = cfg; // Replace default config with config from file
-                config.location = Some(
-                    dunce::canonicalize(file_path)
-                        .unwrap()
-                        .display()
-                        .to_string(),
-                ); // Remember where we found it
-                break;
+            if let Some(file) = read_table(file_path)? {
+                return Ok(Some(file));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Read config table with all configs it extends merged beneath it: in order, so every next one
+/// overrides the previous ones and the file itself overrides all of them.
+/// Extended paths are relative to the file, configs from a git repo use the same syntax as
+/// the remote check, i.e. "https://github.com/org/configs.git#main:checkmark.toml".
+/// None when there is no such file, invalid config(or any config it extends) is an error
+fn read_table(path: &str) -> Result<Option<ConfigFile>, AppError> {
+    read_extended_table(path, &mut vec![])
+}
+
+/// Same as `read_table`, stack holds configs that are being extended to detect cycles
+fn read_extended_table(
+    path: &str,
+    stack: &mut Vec<String>,
+) -> Result<Option<ConfigFile>, AppError> {
+    let Some(mut table) =
+        common::Config::read_table(path).map_err(|message| AppError { message })?
+    else {
+        return Ok(None);
+    };
+    let file_path = absolute_path(path);
+    let base_dir = Path::new(&file_path)
+        .parent()
+        .map(Path::to_path_buf)
+        .unwrap_or_default();
+    resolve_schema_paths(&mut table, &base_dir);
+    let mut origins = HashMap::new();
+    // Config has been validated, so "extends" is a list of paths
+    let extends = match table.remove("extends") {
+        Some(toml::Value::Array(extends)) => extends,
+        _ => {
+            record_origins(&table, "", &file_path, &mut origins);
+            return Ok(Some(ConfigFile {
+                path: file_path,
+                table,
+                origins,
+            }));
+        }
+    };
+    stack.push(file_path.clone());
+    let mut merged = toml::Table::new();
+    // Overrides are accumulated instead of being replaced by the next config
+    let mut overrides = vec![];
+    for extended_path in extends {
+        let Some(extended_path) = extended_path
+            .as_str()
+            .and_then(|extended_path| resolve_extended_path(extended_path, &base_dir))
+        else {
+            continue;
+        };
+        if stack.contains(&absolute_path(&extended_path)) {
+            log::warn!(
+                "Ignoring {} extended by {}: circular extends",
+                &extended_path,
+                &path
+            );
+            continue;
+        }
+        match read_extended_table(&extended_path, stack)? {
+            Some(mut extended) => {
+                if let Some(toml::Value::Array(items)) = extended.table.remove("overrides") {
+                    overrides.extend(items);
+                }
+                common::merge_tables(&mut merged, &extended.table);
+                origins.extend(extended.origins);
+            }
+            None => log::warn!(
+                "Unable to read config {} extended by {}",
+                &extended_path,
+                &path
+            ),
+        }
+    }
+    stack.pop();
+    match table.remove("overrides") {
+        Some(toml::Value::Array(items)) => overrides.extend(items),
+        Some(other) => {
+            table.insert("overrides".to_string(), other);
+        }
+        None => {}
+    }
+    common::merge_tables(&mut merged, &table);
+    record_origins(&table, "", &file_path, &mut origins);
+    if !overrides.is_empty() {
+        merged.insert("overrides".to_string(), toml::Value::Array(overrides));
+    }
+    Ok(Some(ConfigFile {
+        path: file_path,
+        table: merged,
+        origins,
+    }))
+}
+
+/// Front matter schema paths of the config file(and of its overrides) are relative to the file,
+/// make them absolute so they stay the same when configs are merged
+fn resolve_schema_paths(table: &mut toml::Table, base_dir: &Path) {
+    let resolve = |table: &mut toml::Table| {
+        if let Some(toml::Value::String(schema)) = table
+            .get_mut("front_matter")
+            .and_then(toml::Value::as_table_mut)
+            .and_then(|front_matter| front_matter.get_mut("schema"))
+        {
+            *schema = base_dir.join(&*schema).display().to_string();
+        }
+    };
+    if let Some(toml::Value::Array(overrides)) = table.get_mut("overrides") {
+        overrides
+            .iter_mut()
+            .filter_map(toml::Value::as_table_mut)
+            .for_each(resolve);
+    }
+    resolve(table);
+}
+
+/// Remember the file as the origin of every value in the table. Values are either plain
+/// or arrays, so they are replaced as a whole by the next config that sets them
+fn record_origins(
+    table: &toml::Table,
+    prefix: &str,
+    path: &str,
+    origins: &mut HashMap<String, String>,
+) {
+    for (key, value) in table {
+        let key = match prefix.is_empty() {
+            true => key.clone(),
+            false => format!("{}.{}", prefix, key),
+        };
+        match value {
+            toml::Value::Table(table) => record_origins(table, &key, path, origins),
+            _ => {
+                origins.insert(key, path.to_string());
             }
         }
     }
+}
+
+/// Local path of the extended config, git repo is fetched first
+fn resolve_extended_path(path: &str, base_dir: &Path) -> Option<String> {
+    match checkmark_ls::RemoteRepo::parse(path) {
+        Some(remote) => match remote.checkout() {
+            Ok(path) if path.is_file() => Some(path.display().to_string()),
+            Ok(_) => {
+                log::warn!("Extended config {} must point to a file in the repo", &path);
+                None
+            }
+            Err(err) => {
+                log::warn!(
+                    "Unable to fetch extended config {}: {}",
+                    &path,
+                    err.message()
+                );
+                None
+            }
+        },
+        None => Some(base_dir.join(path).display().to_string()),
+    }
+}
+
+fn absolute_path(path: &str) -> String {
+    dunce::canonicalize(path)
+        .map(|path| path.display().to_string())
+        .unwrap_or(path.to_string())
+}
+
+/// First, create one with default values
+/// Then, try reading from TOML file by path provided in CLI
+/// if no CLI option provided - try reading from default locations(replace when found)
+/// and then apply config from CLI because it has higher priority.
+/// Invalid config file is an error rather than being replaced by defaults
+pub fn read_config(
+    cli: &crate::cli::Cli,
+    root_file: Option<&ConfigFile>,
+) -> Result<common::Config, AppError> {
+    let (mut config, _) = read_file_config(root_file)?;
+    apply_cli_options(cli, &mut config);
+    Ok(config)
+}
+
+/// Config built from defaults, the root config file and environment variables(they override the file),
+/// along with origins of the values set by files and variables
+fn read_file_config(
+    root_file: Option<&ConfigFile>,
+) -> Result<(common::Config, HashMap<String, String>), AppError> {
+    log::debug!("Building default config...");
+
+    let mut config = common::Config::default();
+    log::debug!("Default config built: {:#?}", &config);
+
+    log::debug!("Trying to read config from file...");
+    let (env_table, env_origins) = read_env_table(std::env::vars())?;
+    let mut origins = HashMap::new();
+    if let Some(mut file) = root_file.cloned() {
+        // Overrides apply to particular files only, see Configs
+        file.table.remove("overrides");
+        config =
+            common::Config::from_tables(&[file.table, env_table]).map_err(|message| AppError {
+                message: format!("{}: {}", &file.path, message),
+            })?; // Replace default config with config from file
+        config.location = Some(file.path); // Remember where we found it
+        origins = file.origins;
+    } else if !env_table.is_empty() {
+        config =
+            common::Config::from_tables(&[env_table]).map_err(|message| AppError { message })?;
+    }
+    origins.extend(env_origins);
     log::debug!("Config after merging with file: {:#?}", &config);
+    Ok((config, origins))
+}
+
+/// Prefix of environment variables that override config options
+const ENV_PREFIX: &str = "CHECKMARK_";
+
+/// Config table built from `CHECKMARK_<SECTION>_<KEY>` environment variables,
+/// i.e. `CHECKMARK_LINK_CHECKER_TIMEOUT=30`, along with the variable that set every value.
+/// Lists are either comma-separated or TOML arrays, i.e. `*LICENSE*,docs/**` or `["*LICENSE*"]`
+fn read_env_table(
+    vars: impl Iterator<Item = (String, String)>,
+) -> Result<(toml::Table, HashMap<String, String>), AppError> {
+    let mut table = toml::Table::new();
+    let mut origins = HashMap::new();
+    let mut vars = vars
+        .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+        .peekable();
+    if vars.peek().is_none() {
+        return Ok((table, origins));
+    }
+    // Every option of every section along with its JSON Schema, which tells how to parse the value
+    let schema = serde_json::to_value(common::Config::schema()).unwrap_or_default();
+    let mut options = vec![];
+    for (section, property) in schema["properties"].as_object().into_iter().flatten() {
+        let Some(definition) = property["$ref"]
+            .as_str()
+            .and_then(|reference| reference.strip_prefix("#/$defs/"))
+        else {
+            continue;
+        };
+        for (key, property) in schema["$defs"][definition]["properties"]
+            .as_object()
+            .into_iter()
+            .flatten()
+        {
+            options.push((section.clone(), key.clone(), property.clone()));
+        }
+    }
+    for (name, raw) in vars {
+        let Some((section, key, property)) = options.iter().find(|(section, key, _)| {
+            format!("{}{}_{}", ENV_PREFIX, section, key)
+                .to_uppercase()
+                .eq(&name)
+        }) else {
+            log::warn!("Ignoring {}: there is no such config option", &name);
+            continue;
+        };
+        let mut values = toml::Table::new();
+        values.insert(key.clone(), env_value(&raw, property));
+        let mut item = toml::Table::new();
+        item.insert(section.clone(), toml::Value::Table(values));
+        toml::Value::Table(item.clone())
+            .try_into::<common::Config>()
+            .map_err(|err| AppError {
+                message: format!("{}: {}", &name, err.message().trim()),
+            })?;
+        common::merge_tables(&mut table, &item);
+        origins.insert(format!("{}.{}", section, key), name);
+    }
+    log::debug!("Config from environment variables: {:#?}", &table);
+    Ok((table, origins))
+}
+
+/// TOML value of the environment variable, its type is taken from the JSON Schema of the option.
+/// Value that can't be parsed is kept as a string, so validation reports it
+fn env_value(raw: &str, property: &serde_json::Value) -> toml::Value {
+    let parse = |raw: &str| {
+        toml::from_str::<toml::Table>(&format!("value = {}", raw))
+            .ok()
+            .and_then(|mut table| table.remove("value"))
+            .unwrap_or(toml::Value::String(raw.to_string()))
+    };
+    let convert = |raw: &str, property: &serde_json::Value| match json_type(property) {
+        "boolean" | "integer" => parse(raw),
+        _ => toml::Value::String(raw.to_string()),
+    };
+    match json_type(property) {
+        "array" if raw.trim_start().starts_with('[') => parse(raw),
+        "array" => toml::Value::Array(
+            raw.split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| convert(item, &property["items"]))
+                .collect(),
+        ),
+        _ => convert(raw.trim(), property),
+    }
+}
+
+/// JSON type of the option, ignoring "null" of optional ones. Enums are strings
+fn json_type(property: &serde_json::Value) -> &str {
+    match &property["type"] {
+        serde_json::Value::String(kind) => kind,
+        serde_json::Value::Array(kinds) => kinds
+            .iter()
+            .filter_map(|kind| kind.as_str())
+            .find(|kind| !kind.eq(&"null"))
+            .unwrap_or("string"),
+        _ => "string",
+    }
+}
+
+/// Effective root config with the origin of every value: default, config file, environment variable or CLI option.
+/// TOML has origins as comments, JSON has every value as `{"value": ..., "origin": ...}`.
+/// Secrets are masked
+pub fn show_config(
+    cli: &crate::cli::Cli,
+    root_file: Option<&ConfigFile>,
+    format: &crate::cli::ConfigFormat,
+) -> Result<String, AppError> {
+    let to_table = |config: &common::Config| match toml::Value::try_from(config) {
+        Ok(toml::Value::Table(table)) => table,
+        _ => toml::Table::new(),
+    };
+    let (mut config, files) = read_file_config(root_file)?;
+    let before_cli = to_table(&config);
+    apply_cli_options(cli, &mut config);
+    let mut table = to_table(&config);
+
+    let current_dir = std::env::current_dir().unwrap_or_default();
+    let mut origins = BTreeMap::new();
+    for (section, values) in table.iter_mut() {
+        let Some(values) = values.as_table_mut() else {
+            continue;
+        };
+        for (key, value) in values.iter_mut() {
+            let dotted_key = format!("{}.{}", section, key);
+            let origin = if before_cli
+                .get(section)
+                .and_then(|values| values.get(key))
+                .is_none_or(|before| before.ne(value))
+            {
+                match section.as_str() {
+                    "style" => format!("--style-{}", key.replace('_', "-")),
+                    _ => format!("--{}", key.replace('_', "-")),
+                }
+            } else if let Some(path) = files.get(&dotted_key) {
+                Path::new(path)
+                    .strip_prefix(&current_dir)
+                    .map(|path| path.display().to_string())
+                    .unwrap_or(path.clone())
+            } else {
+                "default".to_string()
+            };
+            if ["open_ai.api_key", "link_checker.github_token"].contains(&dotted_key.as_str()) {
+                *value = toml::Value::String("********".to_string());
+            }
+            origins.insert(dotted_key, origin);
+        }
+    }
+
+    Ok(match format {
+        crate::cli::ConfigFormat::Toml => {
+            let mut output = String::new();
+            for (section, values) in &table {
+                let Some(values) = values.as_table().filter(|values| !values.is_empty()) else {
+                    continue;
+                };
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                output.push_str(&format!("[{}]\n", section));
+                for (key, value) in values {
+                    let origin = &origins[&format!("{}.{}", section, key)];
+                    output.push_str(&format!("{} = {} # {}\n", key, value, origin));
+                }
+            }
+            output
+        }
+        crate::cli::ConfigFormat::Json => {
+            let mut json = serde_json::Map::new();
+            for (section, values) in &table {
+                let Some(values) = values.as_table() else {
+                    continue;
+                };
+                let values = values
+                    .iter()
+                    .map(|(key, value)| {
+                        let origin = &origins[&format!("{}.{}", section, key)];
+                        (
+                            key.clone(),
+                            serde_json::json!({ "value": value, "origin": origin }),
+                        )
+                    })
+                    .collect();
+                json.insert(section.clone(), serde_json::Value::Object(values));
+            }
+            serde_json::to_string_pretty(&json).unwrap()
+        }
+    })
+}
+
+/// Dir that contains all checked files, nested configs are looked up until it
+fn project_dir(cli: &crate::cli::Cli) -> Option<PathBuf> {
+    // Remote repo or archive is unpacked into the temp dir
+    if let Some((dir, _)) = checkmark_ls::display_root(&cli.project_root) {
+        return Some(dir);
+    }
+    if cli.project_root.eq(checkmark_ls::STDIN) {
+        return std::env::current_dir().ok();
+    }
+    let path = dunce::canonicalize(&cli.project_root).ok()?;
+    match path.is_dir() {
+        true => Some(path),
+        false => path.parent().map(Path::to_path_buf),
+    }
+}
+
+/// `[[overrides]]` section of a config file: a table that overrides the config
+/// for files matching any of the patterns
+#[derive(Clone)]
+struct Override {
+    files: globset::GlobSet,
+    table: toml::Table,
+}
+
+/// Config file that affects checked files
+#[derive(Clone)]
+struct Layer {
+    /// Where the config file is located
+    path: String,
+    /// Dir that override patterns are relative to
+    base_dir: PathBuf,
+    table: toml::Table,
+    overrides: Vec<Override>,
+}
 
+impl Layer {
+    /// Split `[[overrides]]` out of the config table, override with invalid file patterns is an error
+    fn new(path: String, base_dir: PathBuf, mut table: toml::Table) -> Result<Self, AppError> {
+        let overrides = match table.remove("overrides") {
+            Some(toml::Value::Array(overrides)) => overrides
+                .into_iter()
+                .map(|value| {
+                    parse_override(value).map_err(|message| AppError {
+                        message: format!("Invalid override in {}: {}", &path, message),
+                    })
+                })
+                .collect::<Result<Vec<Override>, AppError>>()?,
+            _ => vec![],
+        };
+        Ok(Self {
+            path,
+            base_dir,
+            table,
+            overrides,
+        })
+    }
+
+    /// Indices of the overrides that apply to the file
+    fn matching_overrides(&self, file_path: &Path) -> Vec<usize> {
+        let relative_path = file_path.strip_prefix(&self.base_dir).unwrap_or(file_path);
+        self.overrides
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.files.is_match(relative_path))
+            .map(|(index, _)| index)
+            .collect()
+    }
+}
+
+fn parse_override(value: toml::Value) -> Result<Override, String> {
+    let toml::Value::Table(mut table) = value else {
+        return Err("must be a table".to_string());
+    };
+    let patterns = match table.remove("files") {
+        Some(toml::Value::Array(patterns)) => patterns,
+        _ => return Err("\"files\" must be a list of file patterns".to_string()),
+    };
+    let mut files = globset::GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = pattern
+            .as_str()
+            .ok_or("\"files\" must be a list of file patterns")?;
+        files.add(globset::Glob::new(pattern).map_err(|err| err.to_string())?);
+    }
+    Ok(Override {
+        files: files.build().map_err(|err| err.to_string())?,
+        table,
+    })
+}
+
+/// Config files(if any) of the visited dirs. Reading them again and again could be slow,
+/// configs they extend could be fetched from remote repos
+#[derive(Default)]
+pub struct DirLayers(HashMap<PathBuf, Option<Layer>>);
+
+/// Effective configs of checked files. Config files located in the project dirs
+/// apply to all files below them: they are merged on top of the root config
+/// from the outermost to the closest one(closest wins) and then CLI options are applied,
+/// because they have the highest priority.
+/// Every config file could override its settings for some files via `[[overrides]]`,
+/// and every document could tune them via the `checkmark` key of its front matter
+pub struct Configs {
+    /// Distinct effective configs, the first one is the root config
+    configs: Vec<common::Config>,
+    /// Paths of all config files that have been read: the root one and nested ones
+    sources: Vec<String>,
+    /// Index of the effective config for every file affected by nested configs or overrides
+    files: HashMap<String, usize>,
+}
+
+impl Configs {
+    pub fn new(
+        cli: &crate::cli::Cli,
+        root: &common::Config,
+        root_file: Option<&ConfigFile>,
+        files: &[common::MarkDownFile],
+    ) -> Result<Self, AppError> {
+        Self::with_dir_layers(cli, root, root_file, files, &mut DirLayers::default())
+    }
+
+    /// Same as `new`, but config files of dirs that have been visited before are not read again
+    pub fn with_dir_layers(
+        cli: &crate::cli::Cli,
+        root: &common::Config,
+        root_file: Option<&ConfigFile>,
+        files: &[common::MarkDownFile],
+        dir_layers: &mut DirLayers,
+    ) -> Result<Self, AppError> {
+        let mut configs = Self {
+            configs: vec![root.clone()],
+            sources: root.location.iter().cloned().collect(),
+            files: HashMap::new(),
+        };
+        if let Some(project_dir) = project_dir(cli) {
+            configs.apply_nested_configs(cli, root, root_file, files, &project_dir, dir_layers)?;
+        }
+        configs.apply_front_matter(files);
+        Ok(configs)
+    }
+
+    /// Merge nested configs and overrides that apply to every file on top of the root config
+    fn apply_nested_configs(
+        &mut self,
+        cli: &crate::cli::Cli,
+        root: &common::Config,
+        root_file: Option<&ConfigFile>,
+        files: &[common::MarkDownFile],
+        project_dir: &Path,
+        dir_layers: &mut DirLayers,
+    ) -> Result<(), AppError> {
+        // Environment variables override all config files
+        let (env_table, _) = read_env_table(std::env::vars())?;
+        // Patterns of the root config are relative to the project dir
+        let root_layer = match root_file {
+            Some(file) => Layer::new(
+                file.path.clone(),
+                project_dir.to_path_buf(),
+                file.table.clone(),
+            )?,
+            None => Layer::new(String::new(), project_dir.to_path_buf(), toml::Table::new())?,
+        };
+        // Effective config of every distinct list of config files and overrides applied
+        let mut chains: HashMap<Vec<(String, Vec<usize>)>, usize> = HashMap::new();
+        for file in files {
+            let file_path = Path::new(&file.path);
+            let mut layers = vec![];
+            if let Some(file_dir) = file_path.parent() {
+                for dir in file_dir
+                    .ancestors()
+                    .take_while(|dir| dir.starts_with(project_dir))
+                {
+                    if !dir_layers.0.contains_key(dir) {
+                        dir_layers.0.insert(dir.to_path_buf(), read_dir_layer(dir)?);
+                    }
+                    if let Some(layer) = &dir_layers.0[dir] {
+                        if !self.sources.contains(&layer.path) {
+                            self.sources.push(layer.path.clone());
+                        }
+                        layers.push(layer.clone());
+                    }
+                }
+            }
+            layers.retain(|layer| {
+                !root
+                    .location
+                    .as_ref()
+                    .is_some_and(|root| root.eq(&layer.path))
+            });
+            layers.push(root_layer.clone());
+            layers.reverse();
+            let chain = layers
+                .iter()
+                .map(|layer| (layer.path.clone(), layer.matching_overrides(file_path)))
+                .collect::<Vec<(String, Vec<usize>)>>();
+            if chain.len() == 1 && chain[0].1.is_empty() {
+                continue;
+            }
+            let index = match chains.get(&chain) {
+                Some(index) => *index,
+                None => {
+                    let mut tables = vec![];
+                    for (layer, (_, overrides)) in layers.iter().zip(&chain) {
+                        tables.push(layer.table.clone());
+                        for index in overrides {
+                            tables.push(layer.overrides[*index].table.clone());
+                        }
+                    }
+                    tables.push(env_table.clone());
+                    let mut config =
+                        common::Config::from_tables(&tables).map_err(|message| AppError {
+                            message: format!("Invalid configs of {}: {}", &file.path, message),
+                        })?;
+                    config.location = layers
+                        .last()
+                        .map(|layer| layer.path.clone())
+                        .filter(|path| !path.is_empty());
+                    apply_cli_options(cli, &mut config);
+                    log::debug!("Config of {:#?}: {:#?}", &chain, &config);
+                    self.configs.push(config);
+                    chains.insert(chain, self.configs.len() - 1);
+                    self.configs.len() - 1
+                }
+            };
+            self.files.insert(file.path.clone(), index);
+        }
+        Ok(())
+    }
+
+    /// Settings from the front matter of the files apply on top of their configs
+    fn apply_front_matter(&mut self, files: &[common::MarkDownFile]) {
+        // Files that share a config and front matter settings share the effective config too
+        let mut memo: HashMap<(usize, String), usize> = HashMap::new();
+        for file in files {
+            let file_config = match common::front_matter::FileConfig::from_file(file) {
+                Ok(Some(file_config)) => file_config,
+                Ok(None) => continue,
+                Err(message) => {
+                    log::warn!("Ignoring front matter of {}: {}", &file.path, message);
+                    continue;
+                }
+            };
+            let base = self.index(file);
+            let index = *memo
+                .entry((base, format!("{:?}", &file_config)))
+                .or_insert_with(|| {
+                    let mut config = self.configs[base].clone();
+                    file_config.apply(&mut config);
+                    log::debug!("Config of {:#?}: {:#?}", &file.path, &config);
+                    self.configs.push(config);
+                    self.configs.len() - 1
+                });
+            self.files.insert(file.path.clone(), index);
+        }
+    }
+
+    /// Config that is used when there are no nested configs
+    pub fn root(&self) -> &common::Config {
+        &self.configs[0]
+    }
+
+    /// Effective config of the file
+    pub fn get(&self, file: &common::MarkDownFile) -> &common::Config {
+        &self.configs[self.index(file)]
+    }
+
+    /// Index of the effective config of the file in `iter()`
+    pub fn index(&self, file: &common::MarkDownFile) -> usize {
+        self.files.get(&file.path).copied().unwrap_or(0)
+    }
+
+    /// All distinct effective configs
+    pub fn iter(&self) -> impl Iterator<Item = &common::Config> {
+        self.configs.iter()
+    }
+
+    /// Paths of all config files that affect checked files
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+}
+
+/// Config file located directly in the dir, its override patterns are relative to the dir
+fn read_dir_layer(dir: &Path) -> Result<Option<Layer>, AppError> {
+    for name in common::CONFIG_FILE_NAMES {
+        let path = dir.join(name).display().to_string();
+        if let Some(file) = read_table(&path)? {
+            return Layer::new(file.path, dir.to_path_buf(), file.table).map(Some);
+        }
+    }
+    Ok(None)
+}
+
+/// Options set via CLI override ones from the config files
+fn apply_cli_options(cli: &crate::cli::Cli, config: &mut common::Config) {
     log::debug!("Merging config with CLI options...");
     match &cli.subcommands {
         crate::cli::Subcommands::Fmt(fmt) => {
@@ -121,11 +812,34 @@ pub fn read_config(cli: &crate::cli::Cli) -> common::Config {
             }
         }
         crate::cli::Subcommands::Spellcheck(_) => {}
+        crate::cli::Subcommands::FrontMatter(front_matter) => {
+            if let Some(schema) = &front_matter.schema {
+                config.front_matter.schema = Some(schema.clone());
+            }
+        }
+        crate::cli::Subcommands::Stale(stale) => {
+            if let Some(max_age_days) = stale.max_age_days {
+                config.stale.max_age_days = max_age_days;
+            }
+        }
         crate::cli::Subcommands::GenerateConfig(_) => {}
+        crate::cli::Subcommands::Init(_) => {}
+        crate::cli::Subcommands::Config(_) => {}
+        crate::cli::Subcommands::Lsp(_) => {}
+    }
+    if cli.stdin_filename.is_some() {
+        config.global.stdin_filename = cli.stdin_filename.clone();
+    }
+    if !cli.include.is_empty() {
+        config.global.include = cli.include.clone();
     }
     if !cli.exclude.is_empty() {
         config.global.exclude = cli.exclude.clone();
     }
+    // Only when user explicitly set this option via CLI - consider it as a force enablement
+    if cli.no_ignore && !config.global.no_ignore {
+        config.global.no_ignore = true;
+    }
     if let Some(style_headings) = &cli.style_headings {
         if style_headings.eq("consistent") {
             config.style.headings = common::HeadingStyle::Consistent;
@@ -167,7 +881,16 @@ pub fn read_config(cli: &crate::cli::Cli) -> common::Config {
     if let Some(default_code_block_language) = &cli.style_default_code_block_language {
         config.style.default_code_block_language = Some(default_code_block_language.clone());
     }
+    if let Some(style_hard_break) = &cli.style_hard_break {
+        if style_hard_break.eq("consistent") {
+            config.style.hard_break = common::HardBreakStyle::Consistent;
+        } else if style_hard_break.eq("backslash") {
+            config.style.hard_break = common::HardBreakStyle::Backslash;
+        } else if style_hard_break.eq("spaces") {
+            config.style.hard_break = common::HardBreakStyle::Spaces;
+        } else {
+            log::warn!("Unknown hard break style: {}", &style_hard_break);
+        }
+    }
     log::debug!("Config after merging with CLI: {:#?}", &config);
-
-    config
 }
@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// Lines added or modified since a git ref provided via --diff-lines.
+/// Used to report only issues introduced by the change
+pub struct ChangedLines {
+    lines: Option<HashMap<String, Vec<RangeInclusive<usize>>>>,
+}
+
+impl ChangedLines {
+    pub fn from(cli: &crate::cli::Cli) -> Result<Self, crate::errors::AppError> {
+        let lines = match &cli.diff_lines {
+            Some(reference) => Some(
+                checkmark_ls::changed_lines(
+                    &cli.project_root,
+                    &checkmark_ls::GitChanges::Since(reference.clone()),
+                )
+                .map_err(|err| crate::errors::AppError {
+                    message: format!("Unable to find changed lines: {}", err.message()),
+                })?,
+            ),
+            None => None,
+        };
+        Ok(Self { lines })
+    }
+
+    /// Drop issues that do not intersect with changed lines of the file.
+    /// Does nothing when --diff-lines is not set
+    pub fn retain_issues(&self, file: &mut common::MarkDownFile) {
+        if let Some(lines) = &self.lines {
+            let ranges = lines.get(&file.path).cloned().unwrap_or_default();
+            file.issues.retain(|issue| {
+                ranges.iter().any(|range| {
+                    issue.row_num_start <= *range.end() && *range.start() <= issue.row_num_end
+                })
+            });
+        }
+    }
+}
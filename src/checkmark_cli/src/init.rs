@@ -0,0 +1,137 @@
+use crate::cli;
+use crate::errors::AppError;
+use colored::Colorize;
+use std::collections::HashMap;
+
+/// Unknown word becomes a project term when it's used at least that many times
+const MIN_TERM_OCCURRENCES: usize = 3;
+
+/// Words that are unknown to the spell checker but used across the project often,
+/// i.e. product names or jargon. Sorted alphabetically, case-insensitively unique
+fn project_terms(files: &[common::MarkDownFile]) -> Vec<String> {
+    let spell_checker = checkmark_spelling::create_spell_checker(&[]);
+    // First spelling of the term along with its count, by lowercase term
+    let mut counts: HashMap<String, (String, usize)> = HashMap::new();
+    for word in files
+        .iter()
+        .flat_map(|file| checkmark_spelling::unknown_words(&spell_checker, file))
+    {
+        counts
+            .entry(word.to_lowercase())
+            .or_insert_with(|| (word, 0))
+            .1 += 1;
+    }
+    let mut terms = counts
+        .into_iter()
+        .filter(|(_, (_, count))| *count >= MIN_TERM_OCCURRENCES)
+        .collect::<Vec<_>>();
+    terms.sort_by(|(a, _), (b, _)| a.cmp(b));
+    terms.into_iter().map(|(_, (term, _))| term).collect()
+}
+
+fn to_toml<T: serde::Serialize>(value: &T) -> String {
+    toml::Value::try_from(value).unwrap().to_string()
+}
+
+/// Options inferred from the files, as (section, key, TOML value).
+/// Options that can't be inferred keep their values from the template
+fn inferred_options(files: &[common::MarkDownFile]) -> Vec<(&'static str, &'static str, String)> {
+    let style = checkmark_fmt::infer_style(files);
+    let mut options = vec![
+        ("style", "headings", to_toml(&style.headings)),
+        ("style", "unordered_lists", to_toml(&style.unordered_lists)),
+        ("style", "bold", to_toml(&style.bold)),
+        ("style", "hard_break", to_toml(&style.hard_break)),
+    ];
+    if let Some(num_spaces) = &style.num_spaces_after_list_marker {
+        options.push(("style", "num_spaces_after_list_marker", to_toml(num_spaces)));
+    }
+    if let Some(language) = &style.default_code_block_language {
+        options.push(("style", "default_code_block_language", to_toml(language)));
+    }
+    options.push((
+        "spelling",
+        "words_whitelist",
+        to_toml(&project_terms(files)),
+    ));
+    options
+}
+
+/// Config template with values of the options replaced, so its comments are kept
+fn tailor_template(options: &[(&str, &str, String)]) -> String {
+    let mut section = "";
+    include_str!("config_template.toml")
+        .lines()
+        .map(|line| {
+            if let Some(name) = line.trim().strip_prefix('[') {
+                section = name.trim_end_matches(']');
+            }
+            let option = options.iter().find(|(option_section, key, _)| {
+                *option_section == section && line.starts_with(&format!("{}=", key))
+            });
+            match option {
+                Some((_, key, value)) => format!("{}={}\n", key, value),
+                None => format!("{}\n", line),
+            }
+        })
+        .collect()
+}
+
+/// Write config with the style that dominates in the files of the project
+/// and its frequently used terms to the project root
+pub fn write_config(
+    cli: &cli::Cli,
+    init: &cli::InitCommand,
+    files: &[common::MarkDownFile],
+) -> Result<(), AppError> {
+    let project_dir = std::path::Path::new(&cli.project_root);
+    if !project_dir.is_dir() {
+        return Err(AppError {
+            message: format!("Unable to init {}: not a directory", &cli.project_root),
+        });
+    }
+    let path = project_dir.join("checkmark.toml");
+    if path.exists() && !init.force {
+        return Err(AppError {
+            message: format!(
+                "{} already exists, use --force to overwrite it",
+                path.display()
+            ),
+        });
+    }
+    let options = inferred_options(files);
+    std::fs::write(&path, tailor_template(&options)).map_err(|err| AppError {
+        message: format!("Unable to write {}: {}", path.display(), err),
+    })?;
+    println!(
+        "{} Inferred from {} file(s), written to {}",
+        "✓".green(),
+        files.len(),
+        path.display()
+    );
+    for (section, key, value) in &options {
+        println!("  {}.{} = {}", section, key, value);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template_values_are_replaced_within_section() {
+        let config = tailor_template(&[
+            ("style", "headings", "\"setext\"".to_string()),
+            ("spelling", "words_whitelist", "[\"kubectl\"]".to_string()),
+        ]);
+        assert!(config.contains("\nheadings=\"setext\"\n"));
+        assert!(config.contains("\nwords_whitelist=[\"kubectl\"]\n"));
+        // Comments and other options are kept
+        assert!(config.contains("\nbold=\"consistent\"\n"));
+        assert!(
+            config.contains("# Example: [\"checkmark\", \"CLI\", \"SARIF\", \"OPEN_AI_API_KEY\"]")
+        );
+        assert!(toml::from_str::<common::Config>(&config).is_ok());
+    }
+}
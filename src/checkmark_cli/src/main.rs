@@ -1,57 +1,91 @@
+mod cache;
 mod cli;
 mod config;
+mod diff;
 mod errors;
+mod init;
 
 use colored::Colorize;
 use rayon::prelude::*;
 
 fn has_any_critical_issue(files: &Vec<common::MarkDownFile>) -> bool {
-    let mut any_critical_issue = false;
-    for file in files {
-        any_critical_issue = !file.issues.is_empty()
-            && file
-                .issues
-                .iter()
-                .any(|issue| issue.severity == common::IssueSeverity::Error);
-    }
-    any_critical_issue
+    files.iter().any(|file| {
+        file.issues.iter().any(|issue| {
+            matches!(
+                issue.severity,
+                common::IssueSeverity::Error | common::IssueSeverity::Bug
+            )
+        })
+    })
 }
 
-#[tokio::main]
-async fn main() -> Result<(), errors::AppError> {
-    // Parse CLI arguments
-    let cli = cli::init();
-
-    // When needed, force enable verbose logging
-    let get_rust_log = |level: &str| {
-        format!("none,checkmark_cli={level},checkmark_fmt={level},checkmark_link_checker={level},checkmark_lint={level},checkmark_ls={level},checkmark_open_ai={level},checkmark_spelling={level},checkmark_render={level},common={level}")
+/// List Markdown files to check. When requested, keep only ones changed in git
+async fn list_files(
+    cli: &cli::Cli,
+    config: &common::Config,
+    tui: &std::sync::Arc<std::sync::Mutex<common::tui::CheckProgressTUI>>,
+) -> Result<Vec<common::MarkDownFile>, errors::AppError> {
+    let mut files = checkmark_ls::ls(&cli.project_root, &config.global, tui).await;
+    let git_changes = match (&cli.changed_since, cli.staged, &cli.diff_lines) {
+        (Some(reference), _, _) => Some(checkmark_ls::GitChanges::Since(reference.clone())),
+        (None, true, _) => Some(checkmark_ls::GitChanges::Staged),
+        (None, false, Some(reference)) => Some(checkmark_ls::GitChanges::Since(reference.clone())),
+        (None, false, None) => None,
     };
-    if cli.verbose {
-        std::env::set_var("RUST_LOG", get_rust_log("debug"))
-    } else {
-        std::env::set_var("RUST_LOG", get_rust_log("warn"))
+    if let Some(git_changes) = &git_changes {
+        let changed_files =
+            checkmark_ls::changed_files(&cli.project_root, git_changes).map_err(|err| {
+                errors::AppError {
+                    message: format!("Unable to find changed files: {}", err.message()),
+                }
+            })?;
+        files.retain(|file| changed_files.contains(&file.path));
     }
-    env_logger::init();
-
-    // Read config
-    let config = config::read_config(&cli);
-
-    // Create TUI
-    let tui = common::tui::CheckProgressTUI::new_thread_safe(cli.ci);
+    Ok(files)
+}
 
-    // Read all MD files
-    let mut files = checkmark_ls::ls(&cli.project_root, &config.global.exclude, &tui).await;
+/// Results of the tool are cached unless disabled
+fn open_cache(cli: &cli::Cli, tool: &str) -> cache::Cache {
+    match cli.no_cache {
+        true => cache::Cache::disabled(),
+        false => cache::Cache::open(&cache::dir(&cli.project_root), tool),
+    }
+}
 
-    // Analyze
+/// Run selected subcommand on the files and collect found issues into them
+async fn analyze(
+    cli: &cli::Cli,
+    configs: &config::Configs,
+    files: &mut Vec<common::MarkDownFile>,
+    changed_lines: &diff::ChangedLines,
+    tui: &std::sync::Arc<std::sync::Mutex<common::tui::CheckProgressTUI>>,
+) {
     match &cli.subcommands {
-        cli::Subcommands::Fmt(_) => match config.fmt.check {
+        cli::Subcommands::Fmt(fmt) if fmt.verify => {
+            tui.lock().unwrap().start_spinner("Verifying formatter...");
+            files.par_iter_mut().for_each(|file| {
+                file.issues
+                    .append(&mut checkmark_fmt::verify(file, configs.get(file)));
+                tui.lock().unwrap().print_file_check_status(file);
+            });
+        }
+        cli::Subcommands::Fmt(_) => match configs.root().fmt.check {
             true => {
                 tui.lock().unwrap().start_spinner("Checking format...");
+                let cache = open_cache(cli, "fmt");
                 files.par_iter_mut().for_each(|file| {
-                    file.issues
-                        .append(&mut checkmark_fmt::check_md_format(file, &config));
+                    let config = configs.get(file);
+                    let settings = (&config.fmt, &config.style);
+                    let mut issues = cache.get(file, &settings).unwrap_or_else(|| {
+                        let issues = checkmark_fmt::check_md_format(file, config);
+                        cache.put(file, &settings, &issues);
+                        issues
+                    });
+                    file.issues.append(&mut issues);
+                    changed_lines.retain_issues(file);
                     tui.lock().unwrap().print_file_check_status(file);
                 });
+                cache.save();
             }
             false => {
                 tui.lock().unwrap().start_spinner("Auto-formatting...");
@@ -61,12 +95,29 @@ async fn main() -> Result<(), errors::AppError> {
                         .bold()
                         .to_string(),
                 );
+                // Document piped via stdin is printed back, there is no file to write into
+                if cli.project_root.eq(checkmark_ls::STDIN) {
+                    for file in files.iter_mut() {
+                        match checkmark_fmt::fmt_markdown(file, configs.get(file)) {
+                            Ok(formatted) => print!("{}", formatted.content),
+                            // Print the document back as is, so piping it doesn't lose it
+                            Err(err) => {
+                                print!("{}", file.content);
+                                file.issues.push(err.to_check_issue(file));
+                            }
+                        }
+                    }
+                    return;
+                }
                 files.par_iter_mut().for_each(|file| {
-                    std::fs::write(
-                        &file.path,
-                        checkmark_fmt::fmt_markdown(file, &config).content,
-                    )
-                    .unwrap();
+                    match checkmark_fmt::fmt_markdown(file, configs.get(file)) {
+                        // Don't touch already formatted files, otherwise watch mode would re-run on them
+                        Ok(formatted) if !formatted.content.eq(&file.content) => {
+                            std::fs::write(&file.path, formatted.content).unwrap();
+                        }
+                        Ok(_) => {}
+                        Err(err) => file.issues.push(err.to_check_issue(file)),
+                    }
                     tui.lock().unwrap().print_file_check_status(file);
                 });
             }
@@ -75,10 +126,11 @@ async fn main() -> Result<(), errors::AppError> {
             tui.lock().unwrap().start_spinner("Reviewing...");
             for file in files.iter_mut() {
                 file.issues.append(
-                    &mut checkmark_open_ai::make_a_review(file, &config)
+                    &mut checkmark_open_ai::make_a_review(file, configs.get(file))
                         .await
                         .unwrap(),
                 );
+                changed_lines.retain_issues(file);
                 tui.lock().unwrap().print_file_check_status(file);
             }
         }
@@ -87,7 +139,7 @@ async fn main() -> Result<(), errors::AppError> {
             tui.lock().unwrap().set_custom_finish_message(
                 &"ʕっ•ᴥ•ʔっ Open out directory".cyan().bold().to_string(),
             );
-            checkmark_render::render(&files, &config).await;
+            checkmark_render::render(files, configs.root()).await;
             tui.lock()
                 .unwrap()
                 .print_file_check_status(&common::MarkDownFile {
@@ -112,9 +164,10 @@ async fn main() -> Result<(), errors::AppError> {
                     .bold()
                     .to_string(),
             );
-            let text = checkmark_open_ai::compose_markdown(&compose_cmd.prompt, &context, &config)
-                .await
-                .unwrap();
+            let text =
+                checkmark_open_ai::compose_markdown(&compose_cmd.prompt, &context, configs.root())
+                    .await
+                    .unwrap();
             std::fs::write(&output_file, &text).unwrap();
             tui.lock()
                 .unwrap()
@@ -126,24 +179,143 @@ async fn main() -> Result<(), errors::AppError> {
         }
         cli::Subcommands::Linkcheck(_) => {
             tui.lock().unwrap().start_spinner("Checking links...");
-            for result in checkmark_link_checker::bulk_check(&files, &config).await {
-                let file = files
-                    .iter_mut()
-                    .find(|file| file.path.eq(&result.path))
-                    .unwrap();
-                file.issues.append(result.issues.clone().as_mut());
-                tui.lock().unwrap().print_file_check_status(file);
+            // Links of all files that share a config are checked at once
+            for (index, config) in configs.iter().enumerate() {
+                if config.link_checker.skip {
+                    continue;
+                }
+                let group = files
+                    .iter()
+                    .filter(|file| configs.index(file) == index)
+                    .cloned()
+                    .collect::<Vec<common::MarkDownFile>>();
+                if group.is_empty() {
+                    continue;
+                }
+                for result in checkmark_link_checker::bulk_check(&group, config).await {
+                    let file = files
+                        .iter_mut()
+                        .find(|file| file.path.eq(&result.path))
+                        .unwrap();
+                    file.issues.append(result.issues.clone().as_mut());
+                    changed_lines.retain_issues(file);
+                    tui.lock().unwrap().print_file_check_status(file);
+                }
             }
         }
         cli::Subcommands::Lint(_) => {
             tui.lock().unwrap().start_spinner("Linting...");
+            let cache = open_cache(cli, "lint");
             files.par_iter_mut().for_each(|file| {
-                file.issues.append(&mut checkmark_lint::lint(file, &config));
+                let config = configs.get(file);
+                if config.linter.skip {
+                    return;
+                }
+                let settings = (&config.linter, &config.style);
+                let mut issues = cache.get(file, &settings).unwrap_or_else(|| {
+                    let issues = checkmark_lint::lint(file, config);
+                    cache.put(file, &settings, &issues);
+                    issues
+                });
+                file.issues.append(&mut issues);
+                changed_lines.retain_issues(file);
                 tui.lock().unwrap().print_file_check_status(file);
             });
+            cache.save();
         }
         cli::Subcommands::Spellcheck(_) => {
-            checkmark_spelling::spell_check_bulk(&mut files, &config, &tui)
+            tui.lock().unwrap().start_spinner("Checking spelling...");
+            let cache = open_cache(cli, "spelling");
+            // Building a spell checker is expensive, don't do that when everything is cached.
+            // Files that share a config share a spell checker too
+            let spell_checkers = configs
+                .iter()
+                .map(|_| std::sync::OnceLock::new())
+                .collect::<Vec<_>>();
+            files.par_iter_mut().for_each(|file| {
+                let config = configs.get(file);
+                if config.spelling.skip {
+                    return;
+                }
+                // Issues suggest adding words to the whitelist of the config they were found with
+                let settings = (&config.spelling, &config.location);
+                let mut issues = cache.get(file, &settings).unwrap_or_else(|| {
+                    let spell_checker = spell_checkers[configs.index(file)].get_or_init(|| {
+                        checkmark_spelling::create_spell_checker(&config.spelling.words_whitelist)
+                    });
+                    let issues = checkmark_spelling::spell_check(spell_checker, file, config);
+                    cache.put(file, &settings, &issues);
+                    issues
+                });
+                file.issues.append(&mut issues);
+                changed_lines.retain_issues(file);
+                tui.lock().unwrap().print_file_check_status(file);
+            });
+            cache.save();
+        }
+        cli::Subcommands::FrontMatter(_) => {
+            tui.lock()
+                .unwrap()
+                .start_spinner("Validating front matter...");
+            // Files that share a config share a schema too
+            let validators = configs
+                .iter()
+                .map(|config| match config.front_matter.skip {
+                    true => Ok(None),
+                    false => checkmark_front_matter::create_validator(config),
+                })
+                .collect::<Vec<_>>();
+            files.par_iter_mut().for_each(|file| {
+                let mut issues = match &validators[configs.index(file)] {
+                    Ok(Some(validator)) => checkmark_front_matter::validate(validator, file),
+                    Ok(None) => return,
+                    Err(message) => vec![checkmark_front_matter::invalid_schema(file, message)],
+                };
+                file.issues.append(&mut issues);
+                changed_lines.retain_issues(file);
+                tui.lock().unwrap().print_file_check_status(file);
+            });
+        }
+        cli::Subcommands::Stale(_) => {
+            tui.lock()
+                .unwrap()
+                .start_spinner("Looking for stale documents...");
+            // Git history is walked once for all files. Document piped via stdin has no history
+            let last_commit_times = match cli.project_root.eq(checkmark_ls::STDIN) {
+                true => Default::default(),
+                false => {
+                    let repo_dir = checkmark_ls::display_root(&cli.project_root)
+                        .map(|(dir, _)| dir.display().to_string())
+                        .unwrap_or(cli.project_root.clone());
+                    let paths = files
+                        .iter()
+                        .map(|file| file.path.clone())
+                        .collect::<Vec<String>>();
+                    checkmark_ls::last_commit_times(&repo_dir, &paths).unwrap_or_else(|err| {
+                        log::warn!(
+                            "Unable to read git history, only front matter dates are used: {}",
+                            err.message()
+                        );
+                        Default::default()
+                    })
+                }
+            };
+            let today = chrono::Local::now().date_naive();
+            files.par_iter_mut().for_each(|file| {
+                let config = configs.get(file);
+                if config.stale.skip {
+                    return;
+                }
+                let mut issues = checkmark_front_matter::check_staleness(
+                    file,
+                    config,
+                    last_commit_times.get(&file.path).copied(),
+                    today,
+                );
+                file.issues.append(&mut issues);
+                changed_lines.retain_issues(file);
+                tui.lock().unwrap().print_file_check_status(file);
+            });
         }
         cli::Subcommands::GenerateConfig(generate_config) => {
             let path = dunce::canonicalize(&generate_config.path)
@@ -152,10 +324,19 @@ async fn main() -> Result<(), errors::AppError> {
             std::fs::write(path, include_str!("config_template.toml"))
                 .expect("Unable to write a file");
         }
+        cli::Subcommands::Init(_) => {}
+        cli::Subcommands::Config(_) => {}
+        cli::Subcommands::Lsp(_) => {}
     }
+}
 
-    // Print all collected check issues
-    tui.lock().unwrap().print_report(&files);
+/// Print all collected check issues and, when requested, save them as SARIF
+fn report(
+    cli: &cli::Cli,
+    files: &Vec<common::MarkDownFile>,
+    tui: &std::sync::Arc<std::sync::Mutex<common::tui::CheckProgressTUI>>,
+) {
+    tui.lock().unwrap().print_report(files);
 
     // When requested - generate SARIF json
     if let Some(file_path) = &cli.sarif {
@@ -175,7 +356,11 @@ async fn main() -> Result<(), errors::AppError> {
                 &mut analyzed_file
                     .issues
                     .iter()
-                    .map(|issue| issue.to_sarif_result())
+                    .map(|issue| {
+                        let mut issue = issue.clone();
+                        issue.file_path = tui.lock().unwrap().display_path(&issue.file_path);
+                        issue.to_sarif_result()
+                    })
                     .collect(),
             );
         }
@@ -197,6 +382,251 @@ async fn main() -> Result<(), errors::AppError> {
         }
         std::fs::write(file_path, serde_json::to_string(&sarif).unwrap()).unwrap();
     }
+}
+
+/// Re-run selected subcommand whenever Markdown files or config file change.
+/// Only changed files are checked again, config change causes re-check of all files
+async fn watch(
+    cli: &cli::Cli,
+    mut config: common::Config,
+    mut root_file: Option<config::ConfigFile>,
+) -> Result<(), errors::AppError> {
+    if let cli::Subcommands::Compose(_)
+    | cli::Subcommands::GenerateConfig(_)
+    | cli::Subcommands::Init(_)
+    | cli::Subcommands::Config(_) = &cli.subcommands
+    {
+        log::warn!("Watch mode is not supported for this command, ignoring --watch");
+        return Ok(());
+    }
+    let config_path = |config: &common::Config| cli.config.clone().or(config.location.clone());
+    let mut watcher =
+        checkmark_ls::Watcher::new(&cli.project_root, &config_path(&config), &config.global)
+            .map_err(|err| errors::AppError {
+                message: format!("Unable to watch {}: {}", &cli.project_root, err),
+            })?;
+    let print_watching = || {
+        println!(
+            "{}",
+            "ʕっ•ᴥ•ʔっ Watching for changes, press Ctrl+C to stop"
+                .cyan()
+                .bold()
+        )
+    };
+    print_watching();
+    while let Some(change) = watcher.next().await {
+        let tui = common::tui::CheckProgressTUI::new_thread_safe(cli.ci);
+        tui.lock().unwrap().clear_screen();
+        let mut files = match change {
+            checkmark_ls::Change::Config => {
+                log::debug!("Config has been changed, re-reading it");
+                // Keep watching with the previous config until the invalid one is fixed
+                match config::read_root_table(cli).and_then(|new_root_file| {
+                    let new_config = config::read_config(cli, new_root_file.as_ref())?;
+                    Ok((new_config, new_root_file))
+                }) {
+                    Ok((new_config, new_root_file)) => {
+                        config = new_config;
+                        root_file = new_root_file;
+                    }
+                    Err(err) => {
+                        log::error!("{}", err);
+                        print_watching();
+                        continue;
+                    }
+                }
+                watcher.set_config_path(&config_path(&config));
+                watcher.set_config(&config.global);
+                list_files(cli, &config, &tui).await?
+            }
+            checkmark_ls::Change::Files(paths) => {
+                let mut files = vec![];
+                for path in &paths {
+                    files.append(&mut checkmark_ls::ls(path, &config.global, &tui).await);
+                }
+                files
+            }
+        };
+        let configs = match config::Configs::new(cli, &config, root_file.as_ref(), &files) {
+            Ok(configs) => configs,
+            Err(err) => {
+                log::error!("{}", err);
+                drop(tui);
+                print_watching();
+                continue;
+            }
+        };
+        analyze(
+            cli,
+            &configs,
+            &mut files,
+            &diff::ChangedLines::from(cli)?,
+            &tui,
+        )
+        .await;
+        report(cli, &files, &tui);
+        drop(tui);
+        print_watching();
+    }
+    Ok(())
+}
+
+/// Configs of documents opened in the editor. Nested configs and front matter could change
+/// between checks, so they are resolved every time, but config files are only read again once they change
+struct LspConfigs {
+    cli: cli::Cli,
+    config: common::Config,
+    root_file: Option<config::ConfigFile>,
+    dir_layers: std::sync::Mutex<config::DirLayers>,
+}
+
+impl checkmark_lsp::Configs for LspConfigs {
+    fn get(&self, file: &common::MarkDownFile) -> common::Config {
+        match config::Configs::with_dir_layers(
+            &self.cli,
+            &self.config,
+            self.root_file.as_ref(),
+            std::slice::from_ref(file),
+            &mut self.dir_layers.lock().unwrap(),
+        ) {
+            Ok(configs) => configs.get(file).clone(),
+            Err(err) => {
+                log::error!("{}", err);
+                self.config.clone()
+            }
+        }
+    }
+
+    fn invalidate(&self) {
+        *self.dir_layers.lock().unwrap() = config::DirLayers::default();
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), errors::AppError> {
+    // Parse CLI arguments
+    let cli = cli::init();
+
+    // When needed, force enable verbose logging
+    let get_rust_log = |level: &str| {
+        format!("none,checkmark={level},checkmark_cli={level},checkmark_fmt={level},checkmark_front_matter={level},checkmark_link_checker={level},checkmark_lint={level},checkmark_ls={level},checkmark_lsp={level},checkmark_open_ai={level},checkmark_spelling={level},checkmark_render={level},common={level}")
+    };
+    if cli.verbose {
+        std::env::set_var("RUST_LOG", get_rust_log("debug"))
+    } else {
+        std::env::set_var("RUST_LOG", get_rust_log("warn"))
+    }
+    env_logger::init();
+
+    // Schema doesn't depend on config files, so it's printed even when they are invalid
+    if let cli::Subcommands::Config(cli::ConfigCommand {
+        subcommands: cli::ConfigSubcommands::Schema,
+    }) = &cli.subcommands
+    {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&common::Config::schema()).unwrap()
+        );
+        return Ok(());
+    }
+
+    // Root config file is resolved once, configs it extends could be fetched from remote repos
+    let root_file = config::read_root_table(&cli)?;
+
+    if let cli::Subcommands::Config(cli::ConfigCommand {
+        subcommands: cli::ConfigSubcommands::Show(show),
+    }) = &cli.subcommands
+    {
+        print!(
+            "{}",
+            config::show_config(&cli, root_file.as_ref(), &show.format)?
+        );
+        return Ok(());
+    }
+
+    // Read config
+    let config = config::read_config(&cli, root_file.as_ref())?;
+
+    // Language server talks to the editor over stdio until it exits,
+    // so it neither scans project files nor prints any report
+    if matches!(cli.subcommands, cli::Subcommands::Lsp(_)) {
+        let configs = LspConfigs {
+            cli,
+            config,
+            root_file,
+            dir_layers: std::sync::Mutex::new(config::DirLayers::default()),
+        };
+        checkmark_lsp::serve(tokio::io::stdin(), tokio::io::stdout(), configs).await;
+        // Runtime would wait for the blocking read of stdin otherwise, which editors may keep open
+        std::process::exit(0);
+    }
+
+    // Reading from stdin means there is neither a dir to watch nor a git repo to diff
+    let from_stdin = cli.project_root.eq(checkmark_ls::STDIN);
+    if from_stdin
+        && (cli.watch || cli.changed_since.is_some() || cli.staged || cli.diff_lines.is_some())
+    {
+        return Err(errors::AppError {
+            message: "--watch, --changed-since, --staged and --diff-lines can't be used when reading from stdin".to_string(),
+        });
+    }
+    if !from_stdin && cli.stdin_filename.is_some() {
+        log::warn!("--stdin-filename is ignored unless project root is \"-\"");
+    }
+
+    // Create TUI
+    let tui = common::tui::CheckProgressTUI::new_thread_safe(cli.ci);
+    // Formatted document goes to stdout, so nothing else shall be printed there
+    if from_stdin
+        && matches!(&cli.subcommands, cli::Subcommands::Fmt(fmt) if !fmt.verify)
+        && !config.fmt.check
+    {
+        tui.lock().unwrap().mute();
+    }
+    // Nothing is checked, validation and init print their own result
+    if let cli::Subcommands::Config(_) | cli::Subcommands::Init(_) = &cli.subcommands {
+        tui.lock().unwrap().mute();
+    }
+
+    // Read all MD files
+    let mut files = list_files(&cli, &config, &tui).await?;
+    // Remote repo and archive are unpacked into the temp dir, report paths relative to them instead
+    if let Some((root, prefix)) = checkmark_ls::display_root(&cli.project_root) {
+        tui.lock().unwrap().set_root(&root, &prefix);
+    }
+
+    // Config files are validated while being read, so reaching here means all of them are valid
+    let configs = config::Configs::new(&cli, &config, root_file.as_ref(), &files)?;
+    if let cli::Subcommands::Config(_) = &cli.subcommands {
+        if configs.sources().is_empty() {
+            println!("No config files found, defaults are used");
+        }
+        for path in configs.sources() {
+            println!("{} {}", "✓".green(), path);
+        }
+        return Ok(());
+    }
+    if let cli::Subcommands::Init(init) = &cli.subcommands {
+        return init::write_config(&cli, init, &files);
+    }
+
+    // Analyze
+    analyze(
+        &cli,
+        &configs,
+        &mut files,
+        &diff::ChangedLines::from(&cli)?,
+        &tui,
+    )
+    .await;
+
+    // Print all collected check issues
+    report(&cli, &files, &tui);
+
+    if cli.watch {
+        drop(tui);
+        return watch(&cli, config, root_file).await;
+    }
 
     if has_any_critical_issue(&files) {
         return Err(errors::AppError {
@@ -0,0 +1,72 @@
+mod utils;
+
+use assert_cmd::Command;
+
+/// Creates a project where checkmark.toml extends shared/base.toml
+fn create_project(name: &str) -> std::path::PathBuf {
+    let base_config = r#"
+[style]
+headings = "setext"
+bold = "asterisk"
+
+[spelling]
+words_whitelist = ["checkmark"]
+"#;
+    let root_config = r#"
+extends = ["shared/base.toml"]
+
+[style]
+headings = "atx"
+
+[link_checker]
+github_token = "secret"
+"#;
+    let files = [
+        ("checkmark.toml", root_config),
+        ("shared/base.toml", base_config),
+        ("README.md", "# Readme\n"),
+    ];
+    utils::create_project("config_show", name, &files)
+}
+
+fn show(dir: &std::path::Path, args: &[&str]) -> String {
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(dir)
+        .args(["config", "show"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn toml_has_origin_of_every_value() {
+    let dir = create_project("toml");
+    let stdout = show(&dir, &["--style-bold", "underscore"]);
+    assert!(stdout.contains("headings = \"atx\" # checkmark.toml\n"));
+    assert!(stdout.contains("bold = \"underscore\" # --style-bold\n"));
+    assert!(stdout.contains("unordered_lists = \"consistent\" # default\n"));
+    assert!(stdout.contains("words_whitelist = [\"checkmark\"] # shared/base.toml\n"));
+    assert!(stdout.contains("github_token = \"********\" # checkmark.toml\n"));
+    assert!(!stdout.contains("secret"));
+    // Output itself is a valid config
+    assert!(toml::from_str::<toml::Table>(&stdout).is_ok());
+}
+
+#[test]
+fn json_has_value_and_origin() {
+    let dir = create_project("json");
+    let stdout = show(&dir, &["--format", "json"]);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(
+        json["style"]["headings"],
+        serde_json::json!({ "value": "atx", "origin": "checkmark.toml" })
+    );
+    assert_eq!(
+        json["style"]["bold"],
+        serde_json::json!({ "value": "asterisk", "origin": "shared/base.toml" })
+    );
+    assert_eq!(json["fmt"]["check"]["origin"], "default");
+}
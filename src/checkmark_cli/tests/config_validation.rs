@@ -0,0 +1,89 @@
+mod utils;
+
+use assert_cmd::Command;
+
+/// Creates a project with a single Markdown file and the given config files
+fn create_project(name: &str, configs: &[(&str, &str)]) -> std::path::PathBuf {
+    let mut files = vec![("docs/guide.md", "# Guide\n")];
+    files.extend_from_slice(configs);
+    utils::create_project("config_validation", name, &files)
+}
+
+fn checkmark(dir: &std::path::Path, args: &[&str]) -> std::process::Output {
+    Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn valid_configs_are_listed() {
+    let dir = create_project(
+        "valid",
+        &[
+            (
+                "checkmark.toml",
+                "[linter]\nmd033_allowed_html_tags = [\"br\"]\n",
+            ),
+            ("docs/checkmark.toml", "[style]\nheadings = \"atx\"\n"),
+        ],
+    );
+    let output = checkmark(&dir, &["config", "validate"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&dir.join("checkmark.toml").display().to_string()));
+    assert!(stdout.contains(&dir.join("docs/checkmark.toml").display().to_string()));
+}
+
+#[test]
+fn unknown_key_fails_with_location() {
+    let dir = create_project(
+        "unknown_key",
+        &[("checkmark.toml", "[linter]\nallowed_html_tags = [\"br\"]\n")],
+    );
+    let output = checkmark(&dir, &["config", "validate"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("checkmark.toml:2:1: unknown field `allowed_html_tags`"));
+
+    // Invalid config is never replaced by defaults
+    let output = checkmark(&dir, &["lint", "--ci", "--no-cache"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("unknown field `allowed_html_tags`"));
+}
+
+#[test]
+fn invalid_nested_config_fails() {
+    let dir = create_project(
+        "invalid_nested",
+        &[("docs/checkmark.toml", "[style]\n\nheadings = \"bold\"\n")],
+    );
+    let output = checkmark(&dir, &["config", "validate"]);
+    assert!(!output.status.success());
+    let location = format!("{}:3:12", dir.join("docs/checkmark.toml").display());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains(&location));
+}
+
+#[test]
+fn generated_config_is_valid() {
+    let dir = create_project("generated", &[]);
+    assert!(checkmark(&dir, &["generate-config"]).status.success());
+    assert!(checkmark(&dir, &["config", "validate"]).status.success());
+}
+
+#[test]
+fn schema_is_printed_even_for_invalid_config() {
+    let dir = create_project("schema", &[("checkmark.toml", "unknown = true\n")]);
+    let output = checkmark(&dir, &["config", "schema"]);
+    assert!(output.status.success());
+    let schema: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(schema["additionalProperties"], serde_json::json!(false));
+    assert!(schema["properties"]["linter"].is_object());
+    assert!(schema["properties"]["overrides"].is_object());
+}
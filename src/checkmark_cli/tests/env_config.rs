@@ -0,0 +1,72 @@
+mod utils;
+
+use assert_cmd::Command;
+
+/// Creates a project where config files set heading style to setext:
+///   - checkmark.toml - for all files
+///   - docs/checkmark.toml - for files in docs/
+fn create_project(name: &str) -> std::path::PathBuf {
+    let files = [
+        ("checkmark.toml", "[style]\nheadings = \"setext\"\n"),
+        ("docs/checkmark.toml", "[style]\nheadings = \"setext\"\n"),
+        ("README.md", "# Readme\n\nLine<br>break\n"),
+        ("docs/guide.md", "# Guide\n\nLine<br>break\n"),
+    ];
+    utils::create_project("env_config", name, &files)
+}
+
+fn checkmark(dir: &std::path::Path) -> Command {
+    let mut command = Command::cargo_bin("checkmark").unwrap();
+    command.current_dir(dir);
+    command
+}
+
+#[test]
+fn env_overrides_config_files() {
+    let dir = create_project("files");
+    let output = checkmark(&dir)
+        .env("CHECKMARK_STYLE_HEADINGS", "atx")
+        .env("CHECKMARK_LINTER_MD033_ALLOWED_HTML_TAGS", "br, img")
+        .args(["fmt", "--check", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = checkmark(&dir)
+        .env("CHECKMARK_STYLE_HEADINGS", "atx")
+        .env("CHECKMARK_LINTER_MD033_ALLOWED_HTML_TAGS", "br, img")
+        .args(["lint", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+}
+
+#[test]
+fn cli_overrides_env() {
+    let dir = create_project("cli");
+    let output = checkmark(&dir)
+        .env("CHECKMARK_STYLE_HEADINGS", "atx")
+        .env("CHECKMARK_LINK_CHECKER_TIMEOUT", "30")
+        .env("CHECKMARK_GLOBAL_EXCLUDE", "[\"*LICENSE*\", \"docs/**\"]")
+        .args(["config", "show", "--style-headings", "consistent"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("headings = \"consistent\" # --style-headings\n"));
+    assert!(stdout.contains("timeout = 30 # CHECKMARK_LINK_CHECKER_TIMEOUT\n"));
+    assert!(stdout.contains("exclude = [\"*LICENSE*\", \"docs/**\"] # CHECKMARK_GLOBAL_EXCLUDE\n"));
+}
+
+#[test]
+fn invalid_env_value_fails() {
+    let dir = create_project("invalid");
+    let output = checkmark(&dir)
+        .env("CHECKMARK_FMT_CHECK", "yes")
+        .args(["lint", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("CHECKMARK_FMT_CHECK: invalid type"));
+}
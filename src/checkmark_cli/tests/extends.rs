@@ -0,0 +1,113 @@
+mod utils;
+
+use assert_cmd::Command;
+
+/// Creates a bare repo with shared config that allows <details> tag
+fn create_shared_repo(dir: &std::path::Path) -> String {
+    let repo = git2::Repository::init_bare(dir.join("shared.git")).unwrap();
+    let config = repo
+        .blob(b"[linter]\nmd033_allowed_html_tags = [\"details\"]\n")
+        .unwrap();
+    let mut tree = repo.treebuilder(None).unwrap();
+    tree.insert("checkmark.toml", config, 0o100644).unwrap();
+    let tree = repo.find_tree(tree.write().unwrap()).unwrap();
+    let signature = git2::Signature::now("checkmark", "checkmark@example.com").unwrap();
+    repo.commit(Some("HEAD"), &signature, &signature, "Shared", &tree, &[])
+        .unwrap();
+    format!(
+        "file://{}#:checkmark.toml",
+        dunce::canonicalize(dir.join("shared.git"))
+            .unwrap()
+            .display()
+    )
+}
+
+/// Creates a project that extends shared configs:
+///   - shared/checkmark.toml - allows <br>, overrides CHANGELOG.md to allow <b>
+///   - project/team.toml - allows <br> and <img>, extends the project config(cycle)
+///   - project/checkmark.toml - extends both of them
+fn create_project(name: &str, extends: &str) -> std::path::PathBuf {
+    let shared_config = r#"
+[linter]
+md033_allowed_html_tags = ["br"]
+[[overrides]]
+files = ["CHANGELOG.md"]
+linter = { md033_allowed_html_tags = ["b"] }
+"#;
+    let team_config = r#"
+extends = ["./checkmark.toml"]
+[linter]
+md033_allowed_html_tags = ["br", "img"]
+"#;
+    let files = [
+        ("shared/checkmark.toml", shared_config),
+        ("project/team.toml", team_config),
+        ("project/checkmark.toml", extends),
+        (
+            "project/README.md",
+            "# Readme\n\nLine<br>break <img src=\"a.png\">\n",
+        ),
+        ("project/CHANGELOG.md", "# Changelog\n\n<b>Bold</b>\n"),
+        (
+            "project/details.md",
+            "# Details\n\n<details>Details</details>\n",
+        ),
+    ];
+    utils::create_project("extends", name, &files)
+}
+
+/// Lint the project and return status line of every file
+fn lint(dir: &std::path::Path) -> Vec<String> {
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(dir.join("project"))
+        .args(["lint", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    let mut statuses = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .filter(|line| line.contains("Ok: ") || line.contains("Has issues: "))
+        .map(|line| {
+            line.trim_start_matches('\r')
+                .replace(&dir.join("project").display().to_string(), "")
+        })
+        .collect::<Vec<String>>();
+    statuses.sort();
+    statuses
+}
+
+#[test]
+fn extended_configs_are_merged_in_order() {
+    let dir = create_project(
+        "merged_in_order",
+        "extends = [\"../shared/checkmark.toml\", \"./team.toml\"]\n",
+    );
+    assert_eq!(
+        lint(&dir),
+        vec![
+            "✓ Ok: /CHANGELOG.md",
+            "✓ Ok: /README.md",
+            "✗ Has issues: /details.md"
+        ]
+    );
+}
+
+#[test]
+fn config_from_git_repo() {
+    let dir = create_project("git_repo", "");
+    let url = create_shared_repo(&dir);
+    std::fs::write(
+        dir.join("project/checkmark.toml"),
+        format!("extends = [\"./team.toml\", \"{url}\"]\n"),
+    )
+    .unwrap();
+    assert_eq!(
+        lint(&dir),
+        vec![
+            "✓ Ok: /details.md",
+            "✗ Has issues: /CHANGELOG.md",
+            "✗ Has issues: /README.md",
+        ]
+    );
+}
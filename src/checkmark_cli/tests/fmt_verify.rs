@@ -0,0 +1,79 @@
+mod utils;
+
+use assert_cmd::Command;
+
+/// Creates a project with a single document that is not formatted
+fn create_project(name: &str) -> std::path::PathBuf {
+    let files = [(
+        "README.md",
+        "# Readme\n\n\n\n* Item\\\n  continued\n* Item\n",
+    )];
+    utils::create_project("fmt_verify", name, &files)
+}
+
+#[test]
+fn verify_does_not_write_files() {
+    let dir = create_project("no_write");
+    let original = std::fs::read_to_string(dir.join("README.md")).unwrap();
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(&dir)
+        .args(["fmt", "--verify", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        original,
+        std::fs::read_to_string(dir.join("README.md")).unwrap()
+    );
+}
+
+#[test]
+fn verify_conflicts_with_check() {
+    let dir = create_project("check");
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(&dir)
+        .args(["fmt", "--verify", "--check", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+/// Output without terminal colors
+fn strip_colors(output: &[u8]) -> String {
+    let output = String::from_utf8_lossy(output);
+    let mut stripped = String::new();
+    let mut chars = output.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\u{1b}' => {
+                chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            }
+            c => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+#[test]
+fn file_is_not_written_when_formatting_changes_its_meaning() {
+    // Inline code with a backtick loses its double backtick fence, which splits the code
+    let files = [("README.md", "# Readme\n\n\n\nUse ``a`b`` here\n")];
+    let dir = utils::create_project("fmt_verify", "refused", &files);
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(&dir)
+        .args(["fmt", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert_eq!(
+        files[0].1.as_bytes(),
+        std::fs::read(dir.join("README.md")).unwrap()
+    );
+    let stderr = strip_colors(&output.stderr);
+    assert!(stderr.contains("formatter bug: formatting changes the meaning of the document"));
+    assert!(stderr.contains("Diff"));
+    assert!(stderr.contains("Use `a`b` here"));
+}
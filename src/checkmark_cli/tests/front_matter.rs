@@ -0,0 +1,49 @@
+mod utils;
+
+use assert_cmd::Command;
+
+/// Creates a project where every Markdown file has <br> tag, some documents allow it via front matter
+fn create_project(name: &str) -> std::path::PathBuf {
+    let files = [
+        ("README.md", "# Readme\n\nLine<br>break\n"),
+        (
+            "disabled_rule.md",
+            "---\ntitle: Guide\ncheckmark:\n  lint:\n    disable: [MD033]\n---\n\n# Guide\n\nLine<br>break\n",
+        ),
+        (
+            "skipped.md",
+            "---\ncheckmark: { lint: false }\n---\n\n# Skipped\n\nLine<br>break\n",
+        ),
+        (
+            "other_rule.md",
+            "---\ncheckmark: { lint: { disable: [MD009] } }\n---\n\n# Other\n\nLine<br>break\n",
+        ),
+        (
+            "invalid.md",
+            "---\ncheckmark: { lnt: false }\n---\n\n# Invalid\n\nLine<br>break\n",
+        ),
+    ];
+    utils::create_project("front_matter", name, &files)
+}
+
+#[test]
+fn front_matter_tunes_checks_of_the_document() {
+    let dir = create_project("lint");
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(&dir)
+        .args(["lint", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let has_issue =
+        |path: &str| stdout.contains(&format!("Has issues: {}", dir.join(path).display()));
+    assert!(has_issue("README.md"));
+    assert!(!has_issue("disabled_rule.md"));
+    assert!(!has_issue("skipped.md"));
+    assert!(has_issue("other_rule.md"));
+    assert!(has_issue("invalid.md"));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Ignoring front matter of"));
+}
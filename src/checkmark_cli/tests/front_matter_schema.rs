@@ -0,0 +1,117 @@
+mod utils;
+
+use assert_cmd::Command;
+
+/// Creates a project where documents under docs/ must have front matter matching the schema
+fn create_project(name: &str) -> std::path::PathBuf {
+    let files = [
+        (
+            "checkmark.toml",
+            "[[overrides]]\nfiles = [\"docs/**\"]\n[overrides.front_matter]\nschema = \"docs.schema.json\"\n",
+        ),
+        (
+            "docs.schema.json",
+            r#"{"type": "object", "required": ["title", "owner"], "properties": {"status": {"enum": ["draft", "published"]}}}"#,
+        ),
+        ("README.md", "# Readme\n"),
+        (
+            "docs/valid.md",
+            "---\ntitle: Guide\nowner: docs-team\nstatus: draft\n---\n\n# Guide\n",
+        ),
+        (
+            "docs/toml.md",
+            "+++\ntitle = \"Guide\"\nowner = \"docs-team\"\n+++\n\n# Guide\n",
+        ),
+        ("docs/no_owner.md", "---\ntitle: Guide\n---\n\n# Guide\n"),
+        (
+            "docs/bad_status.md",
+            "---\ntitle: Guide\nowner: docs-team\nstatus: drafty\n---\n\n# Guide\n",
+        ),
+    ];
+    utils::create_project("front_matter_schema", name, &files)
+}
+
+#[test]
+fn front_matter_is_validated_against_schema_of_matching_files() {
+    let dir = create_project("overrides");
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(&dir)
+        .args(["front-matter", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let has_issue =
+        |path: &str| stdout.contains(&format!("Has issues: {}", dir.join(path).display()));
+    assert!(!has_issue("README.md"));
+    assert!(!has_issue("docs/valid.md"));
+    assert!(!has_issue("docs/toml.md"));
+    assert!(has_issue("docs/no_owner.md"));
+    assert!(has_issue("docs/bad_status.md"));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("\"owner\" is a required property"));
+}
+
+#[test]
+fn missing_schema_is_reported() {
+    let dir = create_project("missing_schema");
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(&dir)
+        .args([
+            "front-matter",
+            "--ci",
+            "--no-cache",
+            "--schema",
+            "none.json",
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Unable to read front matter schema none.json"));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(&format!("Has issues: {}", dir.join("README.md").display())));
+}
+
+#[test]
+fn schema_is_relative_to_the_config_declaring_it() {
+    let dir = create_project("nested_config");
+    let files = [
+        (
+            "guides/checkmark.toml",
+            "[front_matter]\nschema = \"guide.schema.json\"\n",
+        ),
+        (
+            "guides/guide.schema.json",
+            r#"{"type": "object", "required": ["title"]}"#,
+        ),
+        ("guides/titled.md", "---\ntitle: Guide\n---\n\n# Guide\n"),
+        (
+            "guides/untitled.md",
+            "---\nowner: docs-team\n---\n\n# Guide\n",
+        ),
+    ];
+    for (path, content) in files {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(&dir)
+        .args(["front-matter", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let has_issue =
+        |path: &str| stdout.contains(&format!("Has issues: {}", dir.join(path).display()));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(!stderr.contains("Unable to read front matter schema"));
+    assert!(!has_issue("guides/titled.md"));
+    assert!(has_issue("guides/untitled.md"));
+    assert!(stderr.contains("\"title\" is a required property"));
+}
@@ -0,0 +1,72 @@
+mod utils;
+
+use assert_cmd::Command;
+
+/// Creates a project where most documents use setext headings, "*" lists,
+/// "__" bold and Rust code blocks, and mention "kubectl" several times
+fn create_project(name: &str) -> std::path::PathBuf {
+    let files = [
+        (
+            "README.md",
+            "Readme\n======\n\n* Run kubectl\n* Be __bold__\n\n```rust\nfn main() {}\n```\n",
+        ),
+        (
+            "docs/guide.md",
+            "Guide\n=====\n\n*   Use kubectl\n\n```rust\nfn main() {}\n```\n",
+        ),
+        (
+            "docs/other.md",
+            "# Other\n\n- Call kubectl with __great__ care\n\n```sh\nls\n```\n",
+        ),
+        (
+            "docs/setup.md",
+            "Setup\n=====\n\n* Install\n\nNo **strong** opinion on __style__\n",
+        ),
+    ];
+    utils::create_project("init", name, &files)
+}
+
+fn init(dir: &std::path::Path, args: &[&str]) -> std::process::Output {
+    Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(dir)
+        .arg("init")
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn config_is_inferred_from_documents() {
+    let dir = create_project("infer");
+    assert!(init(&dir, &[]).status.success());
+    let config = std::fs::read_to_string(dir.join("checkmark.toml")).unwrap();
+    assert!(config.contains("headings=\"setext\"\n"));
+    assert!(config.contains("unordered_lists=\"asterisk\"\n"));
+    assert!(config.contains("bold=\"underscore\"\n"));
+    assert!(config.contains("num_spaces_after_list_marker=1\n"));
+    assert!(config.contains("default_code_block_language=\"rust\"\n"));
+    assert!(config.contains("words_whitelist=[\"kubectl\"]\n"));
+    // Written config is valid
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(&dir)
+        .args(["config", "validate"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+}
+
+#[test]
+fn existing_config_is_kept_unless_forced() {
+    let dir = create_project("force");
+    std::fs::write(dir.join("checkmark.toml"), "[style]\nheadings = \"atx\"\n").unwrap();
+    let output = init(&dir, &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("use --force to overwrite it"));
+    assert!(init(&dir, &["--force"]).status.success());
+    let config = std::fs::read_to_string(dir.join("checkmark.toml")).unwrap();
+    assert!(config.contains("headings=\"setext\"\n"));
+}
@@ -0,0 +1,161 @@
+mod utils;
+
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+/// Creates a project where every Markdown file has <br> tag,
+/// docs/checkmark.toml disables the rule that reports it for files in docs/
+fn create_project(name: &str) -> std::path::PathBuf {
+    let files = [
+        (
+            "docs/checkmark.toml",
+            "[linter]\ndisabled_rules = [\"MD033\"]\n",
+        ),
+        ("README.md", "# Guide\n\nLine<br>break\n"),
+        ("docs/guide.md", "# Guide\n\nLine<br>break\n"),
+    ];
+    utils::create_project("lsp", name, &files)
+}
+
+/// Language server started in the project dir, talking JSON-RPC over its stdio
+struct Server {
+    process: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+    next_id: u64,
+}
+
+impl Server {
+    fn start(dir: &std::path::Path) -> Self {
+        let mut process = Command::new(assert_cmd::cargo::cargo_bin("checkmark"))
+            .current_dir(dir)
+            .arg("lsp")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let mut server = Self {
+            stdin: process.stdin.take().unwrap(),
+            stdout: BufReader::new(process.stdout.take().unwrap()),
+            process,
+            next_id: 0,
+        };
+        server.request("initialize", json!({ "capabilities": {} }));
+        server.notify("initialized", json!({}));
+        server
+    }
+
+    fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        write!(self.stdin, "Content-Length: {}\r\n\r\n{}", body.len(), body).unwrap();
+        self.stdin.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            self.stdout.read_line(&mut line).unwrap();
+            let line = line.trim();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(length) = line.strip_prefix("Content-Length: ") {
+                content_length = length.parse().unwrap();
+            }
+        }
+        let mut body = vec![0; content_length];
+        self.stdout.read_exact(&mut body).unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    /// Send request and wait for its result, skipping server notifications
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+        loop {
+            let message = self.receive();
+            if message["id"] == json!(id) {
+                return message["result"].clone();
+            }
+        }
+    }
+
+    /// Open the document and wait for its diagnostics, returns their codes
+    fn diagnose(&mut self, path: &std::path::Path) -> Vec<String> {
+        let uri = format!("file://{}", path.display());
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": {
+                    "uri": uri,
+                    "languageId": "markdown",
+                    "version": 1,
+                    "text": std::fs::read_to_string(path).unwrap()
+                }
+            }),
+        );
+        loop {
+            let message = self.receive();
+            if message["method"] == "textDocument/publishDiagnostics"
+                && message["params"]["uri"] == json!(uri)
+            {
+                return message["params"]["diagnostics"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|diagnostic| diagnostic["code"].as_str().unwrap().to_string())
+                    .collect();
+            }
+        }
+    }
+
+    /// Tell the server that the file has been changed on disk
+    fn changed_on_disk(&mut self, path: &std::path::Path) {
+        self.notify(
+            "workspace/didChangeWatchedFiles",
+            json!({ "changes": [{ "uri": format!("file://{}", path.display()), "type": 2 }] }),
+        );
+    }
+
+    fn stop(mut self) {
+        self.request("shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        self.process.wait().unwrap();
+    }
+}
+
+#[test]
+fn nested_config_applies_to_documents() {
+    let dir = create_project("nested_config");
+    let mut server = Server::start(&dir);
+    let readme = server.diagnose(&dir.join("README.md"));
+    let guide = server.diagnose(&dir.join("docs/guide.md"));
+    server.stop();
+    assert!(readme.contains(&"MD033".to_string()));
+    assert!(!guide.contains(&"MD033".to_string()));
+}
+
+#[test]
+fn changed_config_applies_once_server_is_notified() {
+    let dir = create_project("changed_config");
+    let mut server = Server::start(&dir);
+    let guide = dir.join("docs/guide.md");
+    let before = server.diagnose(&guide);
+    let config = dir.join("docs/checkmark.toml");
+    std::fs::write(&config, "[linter]\ndisabled_rules = []\n").unwrap();
+    // Config files are not read again until the server knows they have changed
+    let cached = server.diagnose(&guide);
+    server.changed_on_disk(&config);
+    let after = server.diagnose(&guide);
+    server.stop();
+    assert!(!before.contains(&"MD033".to_string()));
+    assert!(!cached.contains(&"MD033".to_string()));
+    assert!(after.contains(&"MD033".to_string()));
+}
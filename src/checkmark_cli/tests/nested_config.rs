@@ -0,0 +1,53 @@
+mod utils;
+
+use assert_cmd::Command;
+
+/// Creates a project with nested configs:
+///   - checkmark.toml - allows <br>
+///   - docs/api/checkmark.toml - allows <img> only
+///   - docs/guides/checkmark.toml - changes heading style only, so <br> is still allowed
+///
+/// And a Markdown file with <br> tag in every dir
+fn create_project(name: &str) -> std::path::PathBuf {
+    let files = [
+        (
+            "checkmark.toml",
+            "[linter]\nmd033_allowed_html_tags = [\"br\"]\n",
+        ),
+        (
+            "docs/api/checkmark.toml",
+            "[linter]\nmd033_allowed_html_tags = [\"img\"]\n",
+        ),
+        (
+            "docs/guides/checkmark.toml",
+            "[style]\nheadings = \"atx\"\n",
+        ),
+        ("README.md", "# Readme\n\nLine<br>break\n"),
+        ("docs/api/README.md", "# Api\n\nLine<br>break\n"),
+        ("docs/guides/README.md", "# Guides\n\nLine<br>break\n"),
+    ];
+    utils::create_project("nested_config", name, &files)
+}
+
+#[test]
+fn closest_config_wins() {
+    let dir = create_project("closest_wins");
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(&dir)
+        .args(["lint", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let status = |path: &str| {
+        let path = dir.join(path).display().to_string();
+        stdout
+            .lines()
+            .find(|line| line.ends_with(&path))
+            .unwrap()
+            .to_string()
+    };
+    assert!(status("README.md").contains("Ok"));
+    assert!(status("docs/api/README.md").contains("Has issues"));
+    assert!(status("docs/guides/README.md").contains("Ok"));
+}
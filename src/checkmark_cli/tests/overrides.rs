@@ -0,0 +1,55 @@
+mod utils;
+
+use assert_cmd::Command;
+
+/// Creates a project where every Markdown file has <br> tag, which is allowed only by overrides:
+///   - checkmark.toml - allows it in CHANGELOG.md and docs/adr/**
+///   - docs/checkmark.toml - allows it in guide.md(relative to docs/)
+fn create_project(name: &str) -> std::path::PathBuf {
+    let root_config = r#"
+[[overrides]]
+files = ["CHANGELOG.md", "docs/adr/**"]
+[overrides.linter]
+md033_allowed_html_tags = ["br"]
+"#;
+    let docs_config = r#"
+[[overrides]]
+files = ["guide.md"]
+linter = { md033_allowed_html_tags = ["br"] }
+"#;
+    let files = [
+        ("checkmark.toml", root_config),
+        ("docs/checkmark.toml", docs_config),
+        ("README.md", "# Readme\n\nLine<br>break\n"),
+        ("CHANGELOG.md", "# Changelog\n\nLine<br>break\n"),
+        ("docs/adr/001.md", "# Decision\n\nLine<br>break\n"),
+        ("docs/guide.md", "# Guide\n\nLine<br>break\n"),
+        ("docs/api.md", "# Api\n\nLine<br>break\n"),
+    ];
+    utils::create_project("overrides", name, &files)
+}
+
+#[test]
+fn overrides_apply_to_matching_files() {
+    let dir = create_project("matching_files");
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(&dir)
+        .args(["lint", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let status = |path: &str| {
+        let path = dir.join(path).display().to_string();
+        stdout
+            .lines()
+            .find(|line| line.ends_with(&path))
+            .unwrap()
+            .to_string()
+    };
+    assert!(status("README.md").contains("Has issues"));
+    assert!(status("CHANGELOG.md").contains("Ok"));
+    assert!(status("docs/adr/001.md").contains("Ok"));
+    assert!(status("docs/guide.md").contains("Ok"));
+    assert!(status("docs/api.md").contains("Has issues"));
+}
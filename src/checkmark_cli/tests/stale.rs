@@ -0,0 +1,70 @@
+mod utils;
+
+use assert_cmd::Command;
+
+/// Creates a git repo where some documents were committed or reviewed long ago
+fn create_repo(name: &str) -> std::path::PathBuf {
+    let files = [
+        ("old_commit.md", "# Old commit\n"),
+        (
+            "reviewed.md",
+            "---\nlast_reviewed: 2999-01-01\n---\n\n# Reviewed\n",
+        ),
+        (
+            "old_review.md",
+            "---\nlast_reviewed: 2000-01-01\n---\n\n# Old review\n",
+        ),
+    ];
+    let dir = utils::create_project("stale", name, &files);
+    let repo = git2::Repository::init(&dir).unwrap();
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = git2::Signature::new(
+        "checkmark",
+        "checkmark@example.com",
+        &git2::Time::new(1_000_000_000, 0),
+    )
+    .unwrap();
+    repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])
+        .unwrap();
+    std::fs::write(dir.join("untracked.md"), "# Untracked\n").unwrap();
+    dir
+}
+
+#[test]
+fn stale_documents_are_reported() {
+    let dir = create_repo("stale");
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(&dir)
+        .args(["stale", "--ci", "--no-cache"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let has_issue =
+        |path: &str| stdout.contains(&format!("Has issues: {}", dir.join(path).display()));
+    assert!(has_issue("old_commit.md"));
+    assert!(has_issue("old_review.md"));
+    assert!(!has_issue("reviewed.md"));
+    assert!(!has_issue("untracked.md"));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("according to git"));
+}
+
+#[test]
+fn max_age_is_configurable() {
+    let dir = create_repo("max_age");
+    let output = Command::cargo_bin("checkmark")
+        .unwrap()
+        .current_dir(&dir)
+        .args(["stale", "--ci", "--no-cache", "--max-age-days", "100000"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains("Has issues"));
+}
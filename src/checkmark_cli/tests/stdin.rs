@@ -0,0 +1,40 @@
+use assert_cmd::Command;
+
+#[test]
+fn fmt_prints_formatted_stdin_to_stdout() {
+    Command::cargo_bin("checkmark")
+        .unwrap()
+        .args(["fmt", "-", "--ci"])
+        .write_stdin("#   Heading\nText\n")
+        .assert()
+        .success()
+        .stdout("# Heading\n\nText\n");
+}
+
+#[test]
+fn check_reports_stdin_filename() {
+    Command::cargo_bin("checkmark")
+        .unwrap()
+        .args([
+            "fmt",
+            "--check",
+            "-",
+            "--stdin-filename",
+            "docs/README.md",
+            "--ci",
+        ])
+        .write_stdin("#   Heading\nText\n")
+        .assert()
+        .failure()
+        .stdout(predicates::str::contains("docs/README.md"));
+}
+
+#[test]
+fn watch_is_not_supported_for_stdin() {
+    Command::cargo_bin("checkmark")
+        .unwrap()
+        .args(["lint", "-", "--watch"])
+        .write_stdin("# Heading\n")
+        .assert()
+        .failure();
+}
@@ -0,0 +1,17 @@
+/// Creates a project with the files(path relative to the project, content) in a clean temp dir,
+/// which is distinct for every test suite and test
+pub fn create_project(suite: &str, name: &str, files: &[(&str, &str)]) -> std::path::PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("checkmark_{suite}_tests"))
+        .join(name);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    std::fs::create_dir_all(&dir).unwrap();
+    for (path, content) in files {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    dunce::canonicalize(dir).unwrap()
+}
@@ -28,3 +28,30 @@ pub enum Context {
     BlockQuote(BlockQuoteContext),
     BlockQuoteInList(BlockQuoteInListContext),
 }
+
+impl Context {
+    /// New line that continues current block, i.e. "\n> " inside of a block quote.
+    /// Continuation lines are aligned with the content of the block so engines
+    /// do not render them outside of it
+    pub fn new_line(&self) -> String {
+        match self {
+            Context::BlockQuote(ctx) => format!("\n{}", "> ".repeat(ctx.depth)),
+            Context::List(ctx) => {
+                if ctx.is_ordered {
+                    format!("\n{}", "   ".repeat(ctx.nesting_level + 1))
+                } else {
+                    format!("\n{}", "  ".repeat(ctx.nesting_level + 1))
+                }
+            }
+            Context::BlockQuoteInList(ctx) => {
+                // The only difference is in additional space
+                if ctx.list_ctx.is_ordered {
+                    format!("\n{} > ", "  ".repeat(ctx.list_ctx.nesting_level + 1))
+                } else {
+                    format!("\n{}> ", "  ".repeat(ctx.list_ctx.nesting_level + 1))
+                }
+            }
+            Context::Document => String::from("\n"),
+        }
+    }
+}
@@ -0,0 +1,275 @@
+/// This function escapes special characters that has a special meaning Markdown.
+/// Although there's no official specification for how to escape special characters
+/// it is better to do so to avoid any possible issues with engines.
+/// Based on discussion here: https://talk.commonmark.org/t/can-we-have-formal-escaping-rules/2624
+/// and Markdown cookbook: https://bookdown.org/yihui/rmarkdown-cookbook/special-chars.html
+/// and Perforce recommendation: https://www.perforce.com/manuals/v18.2/swarm/Content/Swarm/basics.markdown.html#:~:text=Use%20the%20backslash%20character%20%5C%20to,Exclamation%20point%20%5C!
+/// also this post: https://stackoverflow.com/a/45766624
+/// Do not escape: "-", "+", "!", "#", "{", "}", "(", ")", "_", and "." because render engines are mostly fine with them although they have a special meaning.
+/// Used when minimal escaping would change the meaning of a document
+pub fn escape_all(str: &str) -> String {
+    str.replace('\t', " ")
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace('*', "\\*")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace('>', "\\>")
+        .replace('<', "\\<")
+}
+
+fn is_whitespace(c: Option<char>) -> bool {
+    c.is_none_or(char::is_whitespace)
+}
+
+fn is_punctuation(c: Option<char>) -> bool {
+    c.is_some_and(|c| c.is_ascii_punctuation())
+}
+
+/// "-", "*", "+", "1." or "1)"
+fn is_list_marker(token: &str) -> bool {
+    match token.strip_suffix(['.', ')']) {
+        Some(digits) => {
+            (1..=9).contains(&digits.len()) && digits.chars().all(|c| c.is_ascii_digit())
+        }
+        None => matches!(token, "-" | "*" | "+"),
+    }
+}
+
+/// Line has nothing but block quote and list markers so far, i.e. "> - ".
+/// Block constructs(headings, lists, etc.) can start here
+fn is_line_start(line: &str) -> bool {
+    line.split_whitespace()
+        .all(|token| token.eq(">") || is_list_marker(token))
+}
+
+/// Line renders a table row, i.e. "| Cell | "
+fn is_table_row(line: &str) -> bool {
+    line.trim_start_matches(|c: char| c == '>' || c.is_whitespace())
+        .starts_with('|')
+}
+
+/// Line that would make a table from the text, i.e. "--|--"
+fn is_table_delimiter_row(line: &str) -> bool {
+    line.contains('|')
+        && line.contains('-')
+        && line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' '))
+}
+
+/// Rest of the line that consists of the same character, i.e. "---" or "* * *"
+fn is_thematic_break(rest_of_line: &str, c: char) -> bool {
+    rest_of_line.chars().filter(|ch| *ch == c).count() >= 3
+        && rest_of_line.chars().all(|ch| ch == c || ch == ' ')
+}
+
+/// Delimiter run between prev and next characters can open emphasis or strikethrough
+fn is_left_flanking(prev: Option<char>, next: Option<char>) -> bool {
+    !is_whitespace(next) && (!is_punctuation(next) || is_whitespace(prev) || is_punctuation(prev))
+}
+
+/// Delimiter run between prev and next characters can close emphasis or strikethrough
+fn is_right_flanking(prev: Option<char>, next: Option<char>) -> bool {
+    !is_whitespace(prev) && (!is_punctuation(prev) || is_whitespace(next) || is_punctuation(next))
+}
+
+/// Whether "*" or "_" can open or close emphasis, according to the CommonMark flanking rules
+fn is_emphasis_delimiter(c: char, prev: Option<char>, next: Option<char>) -> bool {
+    let left_flanking = is_left_flanking(prev, next);
+    let right_flanking = is_right_flanking(prev, next);
+    match c {
+        '*' => left_flanking || right_flanking,
+        // Intraword underscores, like in "snake_case", are not emphasis
+        '_' => {
+            (left_flanking && (!right_flanking || is_punctuation(prev)))
+                || (right_flanking && (!left_flanking || is_punctuation(next)))
+        }
+        _ => false,
+    }
+}
+
+/// Rest of the paragraph, it ends with a blank line or a line that starts another block, i.e. a list item
+fn rest_of_paragraph(after: &str) -> &str {
+    let mut end = 0;
+    for (i, line) in after.split_inclusive('\n').enumerate() {
+        let content = line.trim_start_matches(|c: char| c == '>' || c.is_whitespace());
+        let first_token = content.split_whitespace().next().unwrap_or("");
+        if i > 0
+            && (content.is_empty()
+                || (is_list_marker(first_token) && content.len() > first_token.len())
+                || first_token.starts_with('#'))
+        {
+            break;
+        }
+        end += line.len();
+    }
+    &after[..end]
+}
+
+/// Code span(backticks) or strikethrough(tildes) delimiter run opens only when it is closed
+/// later by a run of the same length. Rest is everything that follows the run.
+/// Strikethrough delimiters shall be flanking the same way as the emphasis ones
+fn has_closing_run(c: char, run_len: usize, prev: Option<char>, rest: &[char]) -> bool {
+    if c == '~' && !is_left_flanking(prev, rest.first().copied()) {
+        return false;
+    }
+    let mut i = 0;
+    while i < rest.len() {
+        if rest[i] != c {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while rest.get(i) == Some(&c) {
+            i += 1;
+        }
+        let before_run = start.checked_sub(1).map(|start| rest[start]);
+        if i - start == run_len && (c == '`' || is_right_flanking(before_run, rest.get(i).copied()))
+        {
+            return true;
+        }
+    }
+    false
+}
+
+/// Escape special characters of the text only where they would be parsed as a syntax otherwise,
+/// so "a < b", "x*y" or "snake_case" are kept as is. Before is what has been rendered so far,
+/// its last line(i.e. "> - " in a list inside of a block quote) tells where the text is.
+/// After is the source that follows the text. Delimiters that might pair with ones outside of
+/// the text(at its edges) are escaped too, code span and strikethrough ones - when they are closed
+/// later in the block(paragraph, heading or table cell), possibly outside of the text
+pub fn escape_minimal(text: &str, before: &str, after: &str) -> String {
+    let text = text.replace('\t', " ");
+    let chars = text.chars().collect::<Vec<char>>();
+    let line_before = before.rsplit('\n').next().unwrap_or("");
+    let in_table = is_table_row(line_before);
+    let block_after = match in_table {
+        true => after.split(['\n', '|']).next().unwrap_or(""),
+        false => rest_of_paragraph(after),
+    };
+    // Everything that follows the text in the block, to look for closing delimiters in
+    let text_and_block_after = chars
+        .iter()
+        .copied()
+        .chain(block_after.chars())
+        .collect::<Vec<char>>();
+    let has_table_delimiter_row = text.lines().any(is_table_delimiter_row);
+    let is_edge = |i: usize| i == 0 || i + 1 == chars.len();
+    let count_emphasis_delimiters = |delimiter: char| {
+        (0..chars.len())
+            .filter(|i| chars[*i] == delimiter)
+            .filter(|i| {
+                let prev = i.checked_sub(1).map(|i| chars[i]);
+                is_emphasis_delimiter(delimiter, prev, chars.get(i + 1).copied())
+            })
+            .count()
+    };
+    let num_asterisks = count_emphasis_delimiters('*');
+    let num_underscores = count_emphasis_delimiters('_');
+
+    let mut escaped = String::with_capacity(text.len());
+    // Everything on the current line so far, without escapes
+    let mut line = line_before.to_string();
+    for (i, c) in chars.iter().copied().enumerate() {
+        let prev = match i {
+            0 => line_before.chars().last(),
+            _ => Some(chars[i - 1]),
+        };
+        let next = chars.get(i + 1).copied();
+        let rest_of_line = chars[i..]
+            .iter()
+            .take_while(|c| **c != '\n')
+            .collect::<String>();
+        let at_line_start = is_line_start(&line);
+        let escape = match c {
+            '\\' => next.is_none_or(|next| next == '\n' || next.is_ascii_punctuation()),
+            // Block quote
+            '>' => at_line_start,
+            // Autolink or HTML, i.e. "<https://example.com>" or "<br>"
+            '<' => next
+                .is_none_or(|next| next.is_ascii_alphabetic() || matches!(next, '/' | '!' | '?')),
+            // Heading, or closing sequence of the heading
+            '#' => {
+                let num_hashes = rest_of_line.chars().take_while(|c| *c == '#').count();
+                (at_line_start
+                    && num_hashes <= 6
+                    && is_whitespace(rest_of_line.chars().nth(num_hashes)))
+                    || (line.trim_start_matches(['>', ' ']).starts_with('#')
+                        && is_whitespace(prev)
+                        && rest_of_line.trim_end().chars().all(|c| c == '#'))
+            }
+            // List, thematic break or setext heading underline
+            '-' | '+' => {
+                at_line_start
+                    && (is_whitespace(next)
+                        || is_thematic_break(&rest_of_line, c)
+                        || rest_of_line.trim_end().chars().all(|c| c == '-'))
+            }
+            '=' => at_line_start && rest_of_line.trim_end().chars().all(|c| c == '='),
+            // Ordered list, i.e. "1. Item"
+            '.' | ')' => {
+                let num_digits = line
+                    .chars()
+                    .rev()
+                    .take_while(|c| c.is_ascii_digit())
+                    .count();
+                (1..=9).contains(&num_digits)
+                    && is_line_start(&line[..line.len() - num_digits])
+                    && is_whitespace(next)
+            }
+            '*' | '_' => {
+                let num_delimiters = match c {
+                    '*' => num_asterisks,
+                    _ => num_underscores,
+                };
+                (at_line_start
+                    && ((c == '*' && is_whitespace(next)) || is_thematic_break(&rest_of_line, c)))
+                    || (is_emphasis_delimiter(c, prev, next) && (num_delimiters > 1 || is_edge(i)))
+            }
+            // Code span or strikethrough, its closing delimiters could be in different text nodes
+            // or in the inline code rendered next to the text, i.e. "a `b *c* d` e"
+            '`' | '~' => {
+                let run_start = chars[..i]
+                    .iter()
+                    .rposition(|ch| *ch != c)
+                    .map_or(0, |j| j + 1);
+                let run_end = chars[i..]
+                    .iter()
+                    .position(|ch| *ch != c)
+                    .map_or(chars.len(), |j| i + j);
+                let before_run = match run_start {
+                    0 => line_before.chars().last(),
+                    _ => Some(chars[run_start - 1]),
+                };
+                has_closing_run(
+                    c,
+                    run_end - run_start,
+                    before_run,
+                    &text_and_block_after[run_end..],
+                )
+            }
+            // Link, image or reference
+            '[' => chars[i + 1..].contains(&']') || next.is_none(),
+            ']' => line.contains('[') || i == 0,
+            '|' => in_table || has_table_delimiter_row,
+            // Character reference, i.e. "&amp;" or "&#123;"
+            '&' => {
+                let reference = rest_of_line[1..].trim_start_matches('#');
+                let name_len = reference
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric())
+                    .count();
+                name_len > 0 && reference[name_len..].starts_with(';')
+            }
+            _ => false,
+        };
+        if escape {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+        match c {
+            '\n' => line.clear(),
+            c => line.push(c),
+        }
+    }
+    escaped
+}
@@ -1,30 +1,41 @@
 mod context;
+mod escape;
 mod style;
 mod utils;
+mod verify;
 
 use context::*;
+use escape::*;
 use style::*;
 use utils::*;
 
+pub use style::infer_style;
+pub use verify::{semantic_difference, verify};
+
 use colored::Colorize;
 use markdown::mdast;
 use markdown::mdast::{AlignKind, Node};
 
 /// Takes a table, walks through it's cells, calculates max size of each column
 /// and returns a vector of sizes that represents maximum possible size of each column(per all rows)
-/// Useful to get know what is the expected size of each column to align them later
-fn calculate_max_col_len(table: &mdast::Table, source: &str) -> Vec<usize> {
+/// Useful to get know what is the expected size of each column to align them later.
+/// Cells are measured as they are rendered, escaping may make them shorter or longer than in the source
+fn calculate_max_col_len(
+    table: &mdast::Table,
+    context: &Context,
+    source: &str,
+    options: &FormattingOptions,
+) -> Result<Vec<usize>, String> {
     let mut max_col_len = std::collections::BTreeMap::<usize, usize>::new();
     for child in &table.children {
         if let Node::TableRow(tr) = child {
             for (i, child) in tr.children.iter().enumerate() {
-                let offset_start = child.position().as_ref().unwrap().start.offset;
-                let offset_end = child.position().as_ref().unwrap().end.offset;
-                let col_len = source[offset_start..offset_end]
-                    .trim_matches(' ')
-                    .trim_matches('|')
-                    .trim_matches(' ')
-                    .len();
+                // Cell is rendered in a row, it changes how it is escaped
+                let mut cell = String::from("| ");
+                for child in child.children().into_iter().flatten() {
+                    to_md(child, &mut cell, context, source, options)?;
+                }
+                let col_len = cell.len() - 2;
                 if let Some(max_len) = max_col_len.get(&i) {
                     if col_len > *max_len {
                         max_col_len.insert(i, col_len);
@@ -35,7 +46,7 @@ fn calculate_max_col_len(table: &mdast::Table, source: &str) -> Vec<usize> {
             }
         }
     }
-    max_col_len.into_values().collect()
+    Ok(max_col_len.into_values().collect())
 }
 
 fn render_table_row(
@@ -45,18 +56,22 @@ fn render_table_row(
     source: &str,
     options: &FormattingOptions,
     expected_col_lengths: &[usize],
-) {
+) -> Result<(), String> {
     // Render a heading of the table
     buffer.push_str("| ");
     // Rows
-    for (i, child) in node.children().unwrap().iter().enumerate() {
+    let cells = node.children().ok_or("table row has no cells")?;
+    for (i, child) in cells.iter().enumerate() {
         // Cols
         let len_before = buffer.len();
-        for child in child.children().unwrap() {
-            to_md(child, buffer, context, source, options);
+        for child in child.children().ok_or("table cell has no content")? {
+            to_md(child, buffer, context, source, options)?;
         }
         let len_after = buffer.len();
-        let expected_len = len_before + expected_col_lengths.get(i).unwrap();
+        let expected_len = len_before
+            + expected_col_lengths
+                .get(i)
+                .ok_or("table row has more cells than the table has columns")?;
         if len_after < expected_len {
             // Fill missing with white spaces
             buffer.push_str(&" ".repeat(expected_len - len_after));
@@ -68,6 +83,7 @@ fn render_table_row(
                                                 // Follow it with a separator
                                                 // Remember lengths of each column
                                                 // to align them later
+    Ok(())
 }
 
 fn render_table_heading_separator(
@@ -93,37 +109,18 @@ fn render_table_heading_separator(
     buffer.push('\n');
 }
 
-/// This function escapes special characters that has a special meaning Markdown.
-/// Although there's no official specification for how to escape special characters
-/// it is better to do so to avoid any possible issues with engines.
-/// Based on discussion here: https://talk.commonmark.org/t/can-we-have-formal-escaping-rules/2624
-/// and Markdown cookbook: https://bookdown.org/yihui/rmarkdown-cookbook/special-chars.html
-/// and Perforce recommendation: https://www.perforce.com/manuals/v18.2/swarm/Content/Swarm/basics.markdown.html#:~:text=Use%20the%20backslash%20character%20%5C%20to,Exclamation%20point%20%5C!
-/// also this post: https://stackoverflow.com/a/45766624
-/// Do not escape: "-", "+", "!", "#", "{", "}", "(", ")", "_", and "." because render engines are mostly fine with them although they have a special meaning
-fn escape_special_characters(str: &str) -> String {
-    str.replace('\t', " ")
-        .replace('\\', "\\\\")
-        .replace('|', "\\|")
-        .replace('*', "\\*")
-        .replace('[', "\\[")
-        .replace(']', "\\]")
-        .replace('>', "\\>")
-        .replace('<', "\\<")
-}
-
-/// Render Markdown file from AST
+/// Render Markdown file from AST, error tells what could not be rendered
 fn to_md(
     node: &mdast::Node,
     buffer: &mut String,
     context: &Context,
     source: &str,
     options: &FormattingOptions,
-) {
+) -> Result<(), String> {
     match node {
         Node::Root(r) => {
             for child in &r.children {
-                to_md(child, buffer, context, source, options);
+                to_md(child, buffer, context, source, options)?;
                 buffer.push('\n');
                 // Only when HTML is on a Root-level
                 // we want to add extra newline
@@ -138,7 +135,7 @@ fn to_md(
                 buffer.push(' ');
             }
             for child in &heading.children {
-                to_md(child, buffer, context, source, options);
+                to_md(child, buffer, context, source, options)?;
             }
             buffer.push('\n');
             if HeaderStyle::SetExt == options.header.style {
@@ -150,78 +147,40 @@ fn to_md(
             }
         }
         Node::Text(t) => {
-            let text = escape_special_characters(&t.value);
-            match context {
-                Context::BlockQuote(ctx) => buffer.push_str(
-                    &text.replace('\n', &format!("\n{}", "> ".repeat(ctx.depth).as_str())),
-                ),
-                Context::List(ctx) => {
-                    // Very special case - when we have list with text that has ne lines
-                    // we want to align them
-                    if ctx.is_ordered {
-                        buffer.push_str(&text.replace(
-                            '\n',
-                            &format!("\n{}", "   ".repeat(ctx.nesting_level + 1).as_str()),
-                        ));
-                    } else {
-                        buffer.push_str(&text.replace(
-                            '\n',
-                            &format!("\n{}", "  ".repeat(ctx.nesting_level + 1).as_str()),
-                        ));
-                    }
-                }
-                Context::BlockQuoteInList(ctx) => {
-                    // Very special case - we have a block quote inside a list
-                    // we want to align it with list so it will be rendered
-                    // by engines like a quote inside a list.
-                    // Otherwise - it will be rendered outside
-                    if ctx.list_ctx.is_ordered {
-                        // The only difference is in additional space
-                        buffer.push_str(&text.replace(
-                            '\n',
-                            &format!(
-                                "\n{} > ",
-                                "  ".repeat(ctx.list_ctx.nesting_level + 1).as_str()
-                            ),
-                        ));
-                    } else {
-                        buffer.push_str(&text.replace(
-                            '\n',
-                            &format!(
-                                "\n{}> ",
-                                "  ".repeat(ctx.list_ctx.nesting_level + 1).as_str()
-                            ),
-                        ));
-                    }
+            let text = match options.escaping {
+                Escaping::Minimal => {
+                    let after = t
+                        .position
+                        .as_ref()
+                        .and_then(|position| source.get(position.end.offset..))
+                        .unwrap_or("");
+                    escape_minimal(&t.value, buffer, after)
                 }
-                _ => buffer.push_str(&text),
-            }
+                Escaping::All => escape_all(&t.value),
+            };
+            buffer.push_str(&text.replace('\n', &context.new_line()));
         }
         Node::Paragraph(p) => {
             for child in &p.children {
-                to_md(child, buffer, context, source, options);
+                to_md(child, buffer, context, source, options)?;
             }
             buffer.push('\n');
         }
         Node::List(l) => {
-            let mut start = if l.start.is_some() {
-                l.start.unwrap()
-            } else {
-                0
-            };
+            let mut start = l.start.unwrap_or(0);
             let mut nesting_level = 0;
             if let Context::List(ctx) = context {
                 nesting_level = ctx.nesting_level + 1
             }
             for child in &l.children {
                 if let Context::BlockQuote(_) = context {
-                    if child != l.children.first().unwrap() {
+                    if l.children.first() != Some(child) {
                         buffer.push_str("> ");
                     }
                 }
                 if let Context::List(ctx) = context {
                     if ctx.is_ordered {
-                        if ctx.spread && child == l.children.first().unwrap() {
+                        if ctx.spread && l.children.first() == Some(child) {
                             buffer.push('\n');
                         }
                         buffer.push(' ');
@@ -238,13 +197,13 @@ fn to_md(
                     }),
                     source,
                     options,
-                );
+                )?;
                 start += 1;
                 // Spread list(also called loose in CommonMark) is when
                 // at least one element is new-line separated. We force
                 // to be consistent and add newlines everywhere except
                 // last element because it will have newline anyways
-                if l.spread && child != l.children.last().unwrap() {
+                if l.spread && l.children.last() != Some(child) {
                     buffer.push('\n');
                 }
             }
@@ -277,7 +236,7 @@ fn to_md(
                 for child in &li.children {
                     // When there's 2+ paragraphs in a list item
                     // then we want to align then with list
-                    if child != li.children.first().unwrap() {
+                    if li.children.first() != Some(child) {
                         if let Node::Paragraph(_) = &child {
                             if ctx.is_ordered {
                                 buffer.push_str(&format!(
@@ -297,9 +256,9 @@ fn to_md(
                                 buffer.push_str("  ");
                             }
                         }
-                        to_md(child, buffer, context, source, options);
+                        to_md(child, buffer, context, source, options)?;
                     } else {
-                        to_md(child, buffer, context, source, options);
+                        to_md(child, buffer, context, source, options)?;
                     }
                 }
             }
@@ -335,7 +294,7 @@ fn to_md(
         Node::Emphasis(e) => {
             buffer.push('*');
             for child in &e.children {
-                to_md(child, buffer, context, source, options);
+                to_md(child, buffer, context, source, options)?;
             }
             buffer.push('*');
         }
@@ -345,7 +304,7 @@ fn to_md(
                 StrongStyle::Underscore => buffer.push_str("__"),
             }
             for child in &s.children {
-                to_md(child, buffer, context, source, options);
+                to_md(child, buffer, context, source, options)?;
             }
             match options.strong.style {
                 StrongStyle::Asterisk => buffer.push_str("**"),
@@ -359,7 +318,7 @@ fn to_md(
                 buffer.push_str("~~");
             }
             for child in &d.children {
-                to_md(child, buffer, context, source, options);
+                to_md(child, buffer, context, source, options)?;
             }
             if is_superscript(d, source) {
                 buffer.push('~');
@@ -368,12 +327,17 @@ fn to_md(
             }
         }
         Node::Break(_) => {
-            buffer.push('\n');
+            match options.hard_break.style {
+                HardBreakStyle::Backslash => buffer.push('\\'),
+                HardBreakStyle::Spaces => buffer.push_str("  "),
+            }
+            buffer.push_str(&context.new_line());
         }
         Node::Link(l) => {
             if is_auto_or_bare_link(l) {
+                // Text is the same as URL, except for e-mails where parser adds "mailto:" to the URL
                 buffer.push('<');
-                buffer.push_str(&l.url);
+                buffer.push_str(&l.children.iter().map(Node::to_string).collect::<String>());
                 buffer.push('>');
             } else {
                 buffer.push('[');
@@ -387,10 +351,10 @@ fn to_md(
                         // Every time we format it will add another layer of nesting.
                         // We want to avoid it and render inner link as a plain text.
                         for child in &sub_link.children {
-                            to_md(child, buffer, context, source, options);
+                            to_md(child, buffer, context, source, options)?;
                         }
                     } else {
-                        to_md(child, buffer, context, source, options);
+                        to_md(child, buffer, context, source, options)?;
                     }
                 }
                 buffer.push(']');
@@ -464,15 +428,14 @@ fn to_md(
                         source,
                         options,
                     ),
-                }
+                }?;
                 // Add new trailing blank block quote if there's more than one child
-                if child != b.children.last().unwrap() {
+                if b.children.last() != Some(child) {
                     match &context {
                         Context::Document => buffer.push_str(">\n"),
-                        Context::BlockQuote(ctx) => buffer.push_str(&format!(
-                            "{}\n",
-                            "> ".repeat(ctx.depth + 1).strip_suffix(' ').unwrap()
-                        )),
+                        Context::BlockQuote(ctx) => {
+                            buffer.push_str(&format!("{}\n", "> ".repeat(ctx.depth + 1).trim_end()))
+                        }
                         Context::List(_) => {}
                         Context::BlockQuoteInList(_) => {}
                     }
@@ -506,11 +469,11 @@ fn to_md(
             buffer.push('[');
             for child in &lr.children {
                 match child {
-                    Node::Text(_) => to_md(child, buffer, context, source, options),
+                    Node::Text(_) => to_md(child, buffer, context, source, options)?,
                     Node::Link(l) => {
                         buffer.push_str(&l.url);
                     }
-                    _ => to_md(child, buffer, context, source, options),
+                    _ => to_md(child, buffer, context, source, options)?,
                 }
             }
             buffer.push(']');
@@ -526,11 +489,11 @@ fn to_md(
         Node::FootnoteDefinition(fd) => {
             buffer.push_str(&format!("[^{}]: ", &fd.identifier));
             for child in &fd.children {
-                if child == fd.children.first().unwrap() {
-                    to_md(child, buffer, context, source, options);
+                if fd.children.first() == Some(child) {
+                    to_md(child, buffer, context, source, options)?;
                 } else {
                     let mut tmp_buffer = String::from("");
-                    to_md(child, &mut tmp_buffer, context, source, options);
+                    to_md(child, &mut tmp_buffer, context, source, options)?;
                     if let Some(position) = child.position() {
                         for line in tmp_buffer.lines() {
                             buffer.push_str(&" ".repeat(position.clone().start.column));
@@ -542,7 +505,7 @@ fn to_md(
             }
         }
         Node::Table(t) => {
-            let headers_cols_lengths = calculate_max_col_len(t, source);
+            let headers_cols_lengths = calculate_max_col_len(t, context, source, options)?;
             for child in &t.children {
                 render_table_row(
                     child,
@@ -551,22 +514,22 @@ fn to_md(
                     source,
                     options,
                     &headers_cols_lengths,
-                );
-                if child == t.children.first().unwrap() {
+                )?;
+                if t.children.first() == Some(child) {
                     render_table_heading_separator(t, buffer, &headers_cols_lengths);
                 }
             }
         }
         Node::TableCell(tc) => {
             for child in &tc.children {
-                to_md(child, buffer, context, source, options);
+                to_md(child, buffer, context, source, options)?;
             }
             buffer.push_str(" | ");
         }
         Node::TableRow(tr) => {
             buffer.push_str("| ");
             for child in &tr.children {
-                to_md(child, buffer, context, source, options);
+                to_md(child, buffer, context, source, options)?;
             }
             buffer.push('\n');
         }
@@ -575,36 +538,132 @@ fn to_md(
             buffer.push_str(&yaml.value);
             buffer.push_str("\n---\n");
         }
-        _ => panic!("Unexpected node type {node:#?}"),
+        Node::Toml(toml) => {
+            buffer.push_str("+++\n");
+            buffer.push_str(&toml.value);
+            buffer.push_str("\n+++\n");
+        }
+        // There are no formatting rules for these, keep them as they are
+        Node::Math(_)
+        | Node::MdxjsEsm(_)
+        | Node::MdxFlowExpression(_)
+        | Node::MdxJsxFlowElement(_) => {
+            push_verbatim(node, buffer, source);
+            buffer.push('\n');
+        }
+        Node::InlineMath(_) | Node::MdxTextExpression(_) | Node::MdxJsxTextElement(_) => {
+            push_verbatim(node, buffer, source);
+        }
     }
+    Ok(())
 }
 
-/// Return formatted Markdown file
-pub fn fmt_markdown(file: &common::MarkDownFile, config: &common::Config) -> common::MarkDownFile {
-    log::debug!("Format {:#?} with config: {:#?}", &file.path, &config);
+/// Push source of the node as is
+fn push_verbatim(node: &mdast::Node, buffer: &mut String, source: &str) {
+    match node
+        .position()
+        .and_then(|position| source.get(position.start.offset..position.end.offset))
+    {
+        Some(node_source) => buffer.push_str(node_source),
+        None => log::warn!("Unable to keep node as is, it has no position: {:#?}", node),
+    }
+}
 
-    let fmt_options = FormattingOptions::from(config, file);
-    log::debug!("Formatting options: {:#?}", &fmt_options);
+/// File that can't be formatted, it shall be left as is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FmtError {
+    pub path: String,
+    pub message: String,
+    /// Diff between the file and its formatted version, when formatting has been refused
+    pub diff: Option<String>,
+}
 
-    let mut buffer: String = String::from("");
-    let ast = common::ast::parse(&file.content).unwrap();
-    to_md(
-        &ast,
-        &mut buffer,
-        &Context::Document,
-        &file.content,
-        &fmt_options,
-    );
+impl std::fmt::Display for FmtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unable to format {}: {}", &self.path, &self.message)
+    }
+}
 
-    log::debug!("Removing trailing newlines and spaces");
-    buffer = remove_trailing_newline_and_space(&buffer);
-    buffer.push('\n');
+impl FmtError {
+    /// Report the error as an issue of the file
+    pub fn to_check_issue(&self, file: &common::MarkDownFile) -> common::CheckIssue {
+        let mut issue = common::CheckIssueBuilder::default()
+            .set_category(common::IssueCategory::Formatting)
+            .set_severity(common::IssueSeverity::Bug)
+            .set_file_path(file.path.clone())
+            .set_row_num_start(1)
+            .set_row_num_end(1)
+            .set_col_num_start(1)
+            .set_col_num_end(1)
+            .set_offset_start(0)
+            .set_offset_end(0)
+            .set_message(format!("Unable to format the file: {}", &self.message))
+            .push_fix("💡 The file has been left as is, please report this at https://github.com/vvvar/checkmark/issues");
+        if let Some(diff) = &self.diff {
+            issue = issue.push_fix(&format!("📌 {}\n\n{}\n\n", "Diff".cyan(), diff));
+        }
+        issue.build()
+    }
+}
+
+/// Return formatted Markdown file. A file that can't be parsed(i.e. MDX with unclosed JSX) is an error.
+/// Bugs of the formatter are errors too, so a single file never breaks formatting of others.
+/// Formatted file that means something else than the original one(i.e. lost a hard break) is a bug as well
+pub fn fmt_markdown(
+    file: &common::MarkDownFile,
+    config: &common::Config,
+) -> Result<common::MarkDownFile, FmtError> {
+    log::debug!("Format {:#?} with config: {:#?}", &file.path, &config);
 
-    common::MarkDownFile {
+    let error = |message: String| FmtError {
         path: file.path.clone(),
-        content: buffer,
-        issues: vec![],
+        message,
+        diff: None,
+    };
+    let ast = common::ast::parse_file(file).map_err(|err| error(err.to_string()))?;
+    let render = |escaping: Escaping| {
+        let fmt_options = FormattingOptions {
+            escaping,
+            ..FormattingOptions::from(config, file, &ast)
+        };
+        log::debug!("Formatting options: {:#?}", &fmt_options);
+
+        let mut buffer: String = String::from("");
+        to_md(
+            &ast,
+            &mut buffer,
+            &Context::Document,
+            &file.content,
+            &fmt_options,
+        )
+        .map_err(|message| error(format!("formatter bug: {}", message)))?;
+
+        log::debug!("Removing trailing newlines and spaces");
+        buffer = remove_trailing_newline_and_space(&buffer);
+        buffer.push('\n');
+        Ok(common::MarkDownFile {
+            path: file.path.clone(),
+            content: buffer,
+            issues: vec![],
+        })
+    };
+
+    let mut formatted = render(Escaping::Minimal)?;
+    if verify::semantic_difference(file, &formatted, config).is_some() {
+        log::debug!(
+            "Minimal escaping changes the meaning of {:#?}, escaping all special characters",
+            &file.path
+        );
+        formatted = render(Escaping::All)?;
+    }
+    // Formatting must never change what the document means, better leave it as is
+    if let Some(message) = verify::semantic_difference(file, &formatted, config) {
+        return Err(FmtError {
+            diff: Some(get_diff(&file.content, &formatted.content)),
+            ..error(format!("formatter bug: {}", message))
+        });
     }
+    Ok(formatted)
 }
 
 pub fn check_md_format(
@@ -612,7 +671,10 @@ pub fn check_md_format(
     config: &common::Config,
 ) -> Vec<common::CheckIssue> {
     let mut issues: Vec<common::CheckIssue> = vec![];
-    let formatted = &fmt_markdown(file, config);
+    let formatted = match fmt_markdown(file, config) {
+        Ok(formatted) => formatted,
+        Err(err) => return vec![err.to_check_issue(file)],
+    };
     if !file.content.eq(&formatted.content) {
         let mut issue = common::CheckIssueBuilder::default()
             .set_category(common::IssueCategory::Formatting)
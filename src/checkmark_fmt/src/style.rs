@@ -39,6 +39,15 @@ pub enum StrongStyle {
     Asterisk,
 }
 
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum HardBreakStyle {
+    /// Line ended with "\\"
+    #[default]
+    Backslash,
+    /// Line ended with two spaces
+    Spaces,
+}
+
 #[derive(Debug, Clone, Default, PartialEq)]
 #[allow(dead_code)]
 pub struct HeaderOptions {
@@ -64,76 +73,45 @@ pub struct CodeBlockOptions {
     pub default_language: String,
 }
 
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HardBreakOptions {
+    pub style: HardBreakStyle,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Escaping {
+    /// Escape special characters only where they would be parsed as a syntax
+    #[default]
+    Minimal,
+    /// Escape all special characters, regardless of where they are
+    All,
+}
+
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct FormattingOptions {
     pub header: HeaderOptions,
     pub list: ListOptions,
     pub strong: StrongOptions,
     pub code_block: CodeBlockOptions,
+    pub hard_break: HardBreakOptions,
+    pub escaping: Escaping,
 }
 
 impl FormattingOptions {
-    pub fn from(config: &common::Config, source: &common::MarkDownFile) -> Self {
+    /// Options of the file, AST is the parsed file and "consistent" styles are detected from it
+    pub fn from(
+        config: &common::Config,
+        source: &common::MarkDownFile,
+        ast: &markdown::mdast::Node,
+    ) -> Self {
         Self {
             list: ListOptions {
                 sign_style: match config.style.unordered_lists {
-                    common::UnorderedListStyle::Consistent => {
-                        log::debug!("Detect unordered list style in {:#?}", &source.path);
-                        let ast = common::ast::parse(&source.content).unwrap();
-                        let unordered_list_items = common::ast::BfsIterator::from(&ast)
-                            .filter_map(|n| common::ast::try_cast_to_list(n))
-                            .filter(|l| !l.ordered) // We only care about unordered lists
-                            .flat_map(|l| {
-                                // Get all list items from them
-                                let mut items = vec![];
-                                for child in &l.children {
-                                    if let markdown::mdast::Node::ListItem(li) = child {
-                                        items.push(li);
-                                    }
-                                }
-                                items
-                            })
-                            .collect::<Vec<_>>();
-                        if let Some(first_unordered_list_item) = unordered_list_items.first() {
-                            log::debug!(
-                                "First unordered list item: {:#?}",
-                                &first_unordered_list_item
-                            );
-
-                            let offset_start = first_unordered_list_item
-                                .position
-                                .as_ref()
-                                .unwrap()
-                                .start
-                                .offset;
-                            let offset_end = first_unordered_list_item
-                                .position
-                                .as_ref()
-                                .unwrap()
-                                .end
-                                .offset;
-                            let first_unordered_list_item_str =
-                                &source.content[offset_start..offset_end].trim();
-                            log::debug!(
-                                "Extracted first unordered list item from file: {:#?}",
-                                &first_unordered_list_item_str
-                            );
-
-                            if first_unordered_list_item_str.starts_with('*') {
-                                log::debug!("First unordered list item has asterisk style");
-                                ListSignStyle::Asterisk
-                            } else if first_unordered_list_item_str.starts_with('+') {
-                                log::debug!("First unordered list item has plus style");
-                                ListSignStyle::Plus
-                            } else {
-                                log::debug!("First unordered list style is neither asterisk nor plus, defaulting to dash");
-                                ListSignStyle::Minus
-                            }
-                        } else {
+                    common::UnorderedListStyle::Consistent => detect_list_sign_style(ast, source)
+                        .unwrap_or_else(|| {
                             log::debug!("File has no unordered lists, defaulting to dash");
                             ListSignStyle::Minus
-                        }
-                    }
+                        }),
                     common::UnorderedListStyle::Asterisk => ListSignStyle::Asterisk,
                     common::UnorderedListStyle::Plus => ListSignStyle::Plus,
                     common::UnorderedListStyle::Dash => ListSignStyle::Minus,
@@ -145,62 +123,24 @@ impl FormattingOptions {
             },
             header: HeaderOptions {
                 style: match config.style.headings {
-                    common::HeadingStyle::Consistent => {
-                        log::debug!("Detecting heading style from the file {:#?}", &source.path);
-                        let ast = common::ast::parse(&source.content).unwrap();
-                        let headings = common::ast::BfsIterator::from(&ast)
-                            .filter_map(|n| common::ast::try_cast_to_heading(n))
-                            .collect::<Vec<&markdown::mdast::Heading>>();
-                        if let Some(first_heading) = headings.first() {
-                            log::debug!("First heading in a file: {:#?}", &first_heading);
-
-                            if is_heading_atx(first_heading, &source.content) {
-                                log::debug!("First heading has ATX style");
-                                HeaderStyle::Atx
-                            } else {
-                                log::debug!("First heading has SetExt style");
-                                HeaderStyle::SetExt
-                            }
-                        } else {
+                    common::HeadingStyle::Consistent => detect_header_style(ast, source)
+                        .unwrap_or_else(|| {
                             log::debug!("There are no headings in a file, defaulting to ATX");
                             HeaderStyle::Atx
-                        }
-                    }
+                        }),
                     common::HeadingStyle::Setext => HeaderStyle::SetExt,
                     common::HeadingStyle::Atx => HeaderStyle::Atx,
                 },
             },
             strong: StrongOptions {
                 style: match config.style.bold {
-                    common::BoldStyle::Consistent => {
-                        log::debug!(
-                            "Detecting bold(strong) style from the file {:#?}",
-                            &source.path
-                        );
-
-                        let ast = common::ast::parse(&source.content).unwrap();
-                        let strong_els = common::ast::BfsIterator::from(&ast)
-                            .filter_map(|n| common::ast::try_cast_to_strong(n))
-                            .collect::<Vec<&markdown::mdast::Strong>>();
-                        if let Some(first_strong_el) = strong_els.first() {
-                            log::debug!("First bold(strong) el in a file: {:#?}", &first_strong_el);
-
-                            if is_string_underscored(first_strong_el, &source.content) {
-                                log::debug!("First bold(strong) el is underscored");
-                                StrongStyle::Underscore
-                            } else {
-                                log::debug!(
-                                    "First bold(strong) not underscored, defaulting to the asterisk"
-                                );
-                                StrongStyle::Asterisk
-                            }
-                        } else {
+                    common::BoldStyle::Consistent => detect_strong_style(ast, source)
+                        .unwrap_or_else(|| {
                             log::debug!(
                                 "There are no bold(strong) els in a file, defaulting to asterisk"
                             );
                             StrongStyle::Asterisk
-                        }
-                    }
+                        }),
                     common::BoldStyle::Asterisk => StrongStyle::Asterisk,
                     common::BoldStyle::Underscore => StrongStyle::Underscore,
                 },
@@ -212,10 +152,230 @@ impl FormattingOptions {
                     .clone()
                     .unwrap_or(String::from("text")),
             },
+            hard_break: HardBreakOptions {
+                style: match config.style.hard_break {
+                    common::HardBreakStyle::Consistent => detect_hard_break_style(ast, source)
+                        .unwrap_or_else(|| {
+                            log::debug!(
+                                "There are no hard breaks in a file, defaulting to backslash"
+                            );
+                            HardBreakStyle::Backslash
+                        }),
+                    common::HardBreakStyle::Backslash => HardBreakStyle::Backslash,
+                    common::HardBreakStyle::Spaces => HardBreakStyle::Spaces,
+                },
+            },
+            escaping: Escaping::Minimal,
         }
     }
 }
 
+/// Source of the first unordered list item in the file, i.e. "- Item"
+fn first_unordered_list_item<'a>(
+    ast: &markdown::mdast::Node,
+    source: &'a common::MarkDownFile,
+) -> Option<&'a str> {
+    let first_unordered_list_item = common::ast::BfsIterator::from(ast)
+        .filter_map(|n| common::ast::try_cast_to_list(n))
+        .filter(|l| !l.ordered) // We only care about unordered lists
+        .flat_map(|l| {
+            l.children
+                .iter()
+                .filter_map(common::ast::try_cast_to_list_item)
+        })
+        .next()?;
+    log::debug!(
+        "First unordered list item: {:#?}",
+        &first_unordered_list_item
+    );
+    let position = first_unordered_list_item.position.as_ref()?;
+    let first_unordered_list_item_str =
+        source.content[position.start.offset..position.end.offset].trim();
+    log::debug!(
+        "Extracted first unordered list item from file: {:#?}",
+        &first_unordered_list_item_str
+    );
+    Some(first_unordered_list_item_str)
+}
+
+/// Style of the first unordered list item in the file, None when there are no unordered lists
+pub fn detect_list_sign_style(
+    ast: &markdown::mdast::Node,
+    source: &common::MarkDownFile,
+) -> Option<ListSignStyle> {
+    log::debug!("Detect unordered list style in {:#?}", &source.path);
+    let first_unordered_list_item_str = first_unordered_list_item(ast, source)?;
+    if first_unordered_list_item_str.starts_with('*') {
+        log::debug!("First unordered list item has asterisk style");
+        Some(ListSignStyle::Asterisk)
+    } else if first_unordered_list_item_str.starts_with('+') {
+        log::debug!("First unordered list item has plus style");
+        Some(ListSignStyle::Plus)
+    } else {
+        log::debug!("First unordered list style is neither asterisk nor plus, defaulting to dash");
+        Some(ListSignStyle::Minus)
+    }
+}
+
+/// Amount of spaces after the marker of the first unordered list item in the file,
+/// None when there are no unordered lists or the first item is empty
+pub fn detect_num_spaces_after_list_marker(
+    ast: &markdown::mdast::Node,
+    source: &common::MarkDownFile,
+) -> Option<u8> {
+    let first_unordered_list_item_str = first_unordered_list_item(ast, source)?;
+    let num_spaces = first_unordered_list_item_str
+        .chars()
+        .skip(1) // List marker
+        .take_while(|c| *c == ' ')
+        .count();
+    match num_spaces {
+        0 => None,
+        num_spaces => u8::try_from(num_spaces).ok(),
+    }
+}
+
+/// Style of the first heading in the file, None when there are no headings
+pub fn detect_header_style(
+    ast: &markdown::mdast::Node,
+    source: &common::MarkDownFile,
+) -> Option<HeaderStyle> {
+    log::debug!("Detecting heading style from the file {:#?}", &source.path);
+    let first_heading =
+        common::ast::BfsIterator::from(ast).find_map(|n| common::ast::try_cast_to_heading(n))?;
+    log::debug!("First heading in a file: {:#?}", &first_heading);
+    if is_heading_atx(first_heading, &source.content) {
+        log::debug!("First heading has ATX style");
+        Some(HeaderStyle::Atx)
+    } else {
+        log::debug!("First heading has SetExt style");
+        Some(HeaderStyle::SetExt)
+    }
+}
+
+/// Style of the first bold(strong) element in the file, None when there are no such elements
+pub fn detect_strong_style(
+    ast: &markdown::mdast::Node,
+    source: &common::MarkDownFile,
+) -> Option<StrongStyle> {
+    log::debug!(
+        "Detecting bold(strong) style from the file {:#?}",
+        &source.path
+    );
+    let first_strong_el =
+        common::ast::BfsIterator::from(ast).find_map(|n| common::ast::try_cast_to_strong(n))?;
+    log::debug!("First bold(strong) el in a file: {:#?}", &first_strong_el);
+    if is_string_underscored(first_strong_el, &source.content) {
+        log::debug!("First bold(strong) el is underscored");
+        Some(StrongStyle::Underscore)
+    } else {
+        log::debug!("First bold(strong) not underscored, defaulting to the asterisk");
+        Some(StrongStyle::Asterisk)
+    }
+}
+
+/// Style of the first hard break in the file, None when there are no hard breaks
+pub fn detect_hard_break_style(
+    ast: &markdown::mdast::Node,
+    source: &common::MarkDownFile,
+) -> Option<HardBreakStyle> {
+    log::debug!(
+        "Detecting hard break style from the file {:#?}",
+        &source.path
+    );
+    let first_break =
+        common::ast::BfsIterator::from(ast).find_map(|n| common::ast::try_cast_to_break(n))?;
+    log::debug!("First hard break in a file: {:#?}", &first_break);
+    if is_break_backslash(first_break, &source.content) {
+        log::debug!("First hard break has backslash style");
+        Some(HardBreakStyle::Backslash)
+    } else {
+        log::debug!("First hard break has spaces style");
+        Some(HardBreakStyle::Spaces)
+    }
+}
+
+/// Languages of all code blocks in the file, in order of appearance
+pub fn detect_code_block_languages(ast: &markdown::mdast::Node) -> Vec<String> {
+    common::ast::BfsIterator::from(ast)
+        .filter_map(|n| common::ast::try_cast_to_code(n))
+        .filter_map(|code| code.lang.clone())
+        .collect()
+}
+
+/// The most frequent value, the first seen one wins a tie. None when there are no values
+fn dominant<T: PartialEq>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    let mut counts: Vec<(T, usize)> = vec![];
+    for value in values {
+        match counts.iter_mut().find(|(v, _)| *v == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((value, 1)),
+        }
+    }
+    let max = counts.iter().map(|(_, count)| *count).max()?;
+    counts
+        .into_iter()
+        .find(|(_, count)| *count == max)
+        .map(|(value, _)| value)
+}
+
+/// Style that dominates in the parsed files, None when none of them has such elements
+fn dominant_style<T: PartialEq>(
+    parsed: &[(markdown::mdast::Node, &common::MarkDownFile)],
+    detect_style: impl Fn(&markdown::mdast::Node, &common::MarkDownFile) -> Option<T>,
+) -> Option<T> {
+    dominant(
+        parsed
+            .iter()
+            .filter_map(|(ast, file)| detect_style(ast, file)),
+    )
+}
+
+/// Infer style that dominates in the files, i.e. the one most of them use.
+/// Options that can't be inferred(no such elements in files) are left "consistent".
+/// Files that can't be parsed are skipped
+pub fn infer_style(files: &[common::MarkDownFile]) -> common::StyleConfig {
+    let parsed = files
+        .iter()
+        .filter_map(|file| match common::ast::parse_file(file) {
+            Ok(ast) => Some((ast, file)),
+            Err(err) => {
+                log::warn!("Unable to infer style of {:#?}: {}", &file.path, err);
+                None
+            }
+        })
+        .collect::<Vec<_>>();
+    common::StyleConfig {
+        headings: match dominant_style(&parsed, detect_header_style) {
+            Some(HeaderStyle::Atx) => common::HeadingStyle::Atx,
+            Some(HeaderStyle::SetExt) => common::HeadingStyle::Setext,
+            None => common::HeadingStyle::Consistent,
+        },
+        unordered_lists: match dominant_style(&parsed, detect_list_sign_style) {
+            Some(ListSignStyle::Minus) => common::UnorderedListStyle::Dash,
+            Some(ListSignStyle::Asterisk) => common::UnorderedListStyle::Asterisk,
+            Some(ListSignStyle::Plus) => common::UnorderedListStyle::Plus,
+            None => common::UnorderedListStyle::Consistent,
+        },
+        num_spaces_after_list_marker: dominant_style(&parsed, detect_num_spaces_after_list_marker),
+        bold: match dominant_style(&parsed, detect_strong_style) {
+            Some(StrongStyle::Asterisk) => common::BoldStyle::Asterisk,
+            Some(StrongStyle::Underscore) => common::BoldStyle::Underscore,
+            None => common::BoldStyle::Consistent,
+        },
+        hard_break: match dominant_style(&parsed, detect_hard_break_style) {
+            Some(HardBreakStyle::Backslash) => common::HardBreakStyle::Backslash,
+            Some(HardBreakStyle::Spaces) => common::HardBreakStyle::Spaces,
+            None => common::HardBreakStyle::Consistent,
+        },
+        default_code_block_language: dominant(
+            parsed
+                .iter()
+                .flat_map(|(ast, _)| detect_code_block_languages(ast)),
+        ),
+    }
+}
+
 /// It is possible to pass single "~" and it wold be interpreted
 /// as a delete which shall be interpreted as a superscript
 /// https://github.com/markdown-it/markdown-it-sup
@@ -254,6 +414,16 @@ pub fn is_heading_atx(d: &markdown::mdast::Heading, source: &str) -> bool {
     atx
 }
 
+/// There are two types of hard break - backslash("\\") and spaces("  ") before the line ending.
+/// We can determine a type of it from the original file
+pub fn is_break_backslash(b: &markdown::mdast::Break, source: &str) -> bool {
+    let mut backslash = false;
+    if let Some(position) = &b.position {
+        backslash = source[position.start.offset..].starts_with('\\');
+    }
+    backslash
+}
+
 /// Returns true when link it either auto or bare
 /// Autolink: "<http://example.com>"
 /// Bare link: "http://example.com"
@@ -264,7 +434,7 @@ pub fn is_auto_or_bare_link(l: &markdown::mdast::Link) -> bool {
     //       title: None
     //    }
     if l.title.is_none() && l.children.len() == 1 {
-        if let markdown::mdast::Node::Text(t) = l.children.first().unwrap() {
+        if let Some(markdown::mdast::Node::Text(t)) = l.children.first() {
             // Check that text is === as url
             // "mailto:" stripped because parser adds it
             // for all e-mails
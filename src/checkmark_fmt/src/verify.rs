@@ -0,0 +1,167 @@
+use crate::{fmt_markdown, get_diff, FmtError};
+use serde_json::Value;
+
+/// Document tree that is compared to tell whether two documents mean the same.
+/// Positions are dropped, so is whether lists are loose or tight(formatter is free to choose),
+/// whitespace in text is collapsed(it is rendered as a single space)
+/// and code blocks without a language get the default one, just like the formatter does
+fn normalized_ast(
+    file: &common::MarkDownFile,
+    default_code_block_language: &str,
+) -> Result<Value, String> {
+    let ast = common::ast::parse_file(file).map_err(|err| err.to_string())?;
+    let mut value = serde_json::to_value(&ast).map_err(|err| err.to_string())?;
+    normalize(&mut value, default_code_block_language);
+    Ok(value)
+}
+
+fn normalize(value: &mut Value, default_code_block_language: &str) {
+    match value {
+        Value::Object(node) => {
+            node.remove("position");
+            node.remove("spread");
+            match node.get("type").and_then(Value::as_str) {
+                Some("text") => {
+                    if let Some(Value::String(text)) = node.get_mut("value") {
+                        *text = collapse_whitespace(text);
+                    }
+                }
+                Some("code") if node.get("lang").is_none_or(Value::is_null) => {
+                    node.insert(
+                        String::from("lang"),
+                        Value::String(default_code_block_language.to_string()),
+                    );
+                }
+                _ => {}
+            }
+            for child in node.values_mut() {
+                normalize(child, default_code_block_language);
+            }
+        }
+        Value::Array(children) => {
+            for child in children {
+                normalize(child, default_code_block_language);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Every run of whitespace becomes a single space, including ones at the edges:
+/// they separate the text from its siblings, i.e. "a *b*" is not the same as "a*b*"
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut after_whitespace = false;
+    for c in text.chars() {
+        match c.is_whitespace() {
+            true if after_whitespace => {}
+            true => collapsed.push(' '),
+            false => collapsed.push(c),
+        }
+        after_whitespace = c.is_whitespace();
+    }
+    collapsed
+}
+
+/// Describes how the formatted file differs in meaning from the original one,
+/// None when both are the same document
+pub fn semantic_difference(
+    original: &common::MarkDownFile,
+    formatted: &common::MarkDownFile,
+    config: &common::Config,
+) -> Option<String> {
+    if original.content.eq(&formatted.content) {
+        return None;
+    }
+    let default_code_block_language = config
+        .style
+        .default_code_block_language
+        .clone()
+        .unwrap_or(String::from("text"));
+    let formatted_ast = match normalized_ast(formatted, &default_code_block_language) {
+        Ok(ast) => ast,
+        Err(err) => return Some(format!("formatted file can't be parsed: {}", err)),
+    };
+    match normalized_ast(original, &default_code_block_language) {
+        Ok(original_ast) if original_ast.ne(&formatted_ast) => {
+            log::debug!(
+                "Formatting of {:#?} changes its meaning:\n{}",
+                &original.path,
+                get_diff(
+                    &serde_json::to_string_pretty(&original_ast).unwrap_or_default(),
+                    &serde_json::to_string_pretty(&formatted_ast).unwrap_or_default()
+                )
+            );
+            Some(String::from(
+                "formatting changes the meaning of the document",
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// Variants of the file to fuzz the formatter with: the file itself and
+/// the file with every single line removed, as (removed line number, file)
+fn variants(file: &common::MarkDownFile) -> Vec<(Option<usize>, common::MarkDownFile)> {
+    let lines = file.content.lines().collect::<Vec<&str>>();
+    let mut variants = vec![(None, file.clone())];
+    for removed in 0..lines.len() {
+        let mut content = lines
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != removed)
+            .map(|(_, line)| *line)
+            .collect::<Vec<&str>>()
+            .join("\n");
+        content.push('\n');
+        variants.push((
+            Some(removed + 1),
+            common::MarkDownFile {
+                path: file.path.clone(),
+                content,
+                issues: vec![],
+            },
+        ));
+    }
+    variants
+}
+
+/// Fuzz the formatter with the file without writing anything. The file and every variant of it with a
+/// single line removed must be formatted without errors, keep its meaning and stay the same when formatted again.
+/// Every distinct problem is reported once, as a bug of the formatter
+pub fn verify(file: &common::MarkDownFile, config: &common::Config) -> Vec<common::CheckIssue> {
+    log::debug!("Verify formatting of {:#?}", &file.path);
+    let mut errors: Vec<FmtError> = vec![];
+    for (removed_line, variant) in variants(file) {
+        let error = match fmt_markdown(&variant, config) {
+            Ok(formatted) => match fmt_markdown(&formatted, config) {
+                Ok(reformatted) if reformatted.content.ne(&formatted.content) => FmtError {
+                    path: file.path.clone(),
+                    message: String::from("formatter bug: formatting is not idempotent"),
+                    diff: Some(get_diff(&formatted.content, &reformatted.content)),
+                },
+                Ok(_) => continue,
+                Err(err) => err,
+            },
+            Err(err) => err,
+        };
+        // Variant of a file that can't be parsed is not a bug of the formatter
+        if !error.message.starts_with("formatter bug") {
+            continue;
+        }
+        if errors.iter().any(|e| e.message.starts_with(&error.message)) {
+            continue;
+        }
+        errors.push(FmtError {
+            message: match removed_line {
+                Some(line) => format!("{} (when line {} is removed)", &error.message, line),
+                None => error.message,
+            },
+            ..error
+        });
+    }
+    errors
+        .iter()
+        .map(|error| error.to_check_issue(file))
+        .collect()
+}
@@ -0,0 +1,53 @@
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+mod utils;
+
+fn fmt_with_style(source: &str, style: common::HardBreakStyle) -> String {
+    let mut config = common::Config::default();
+    config.style.hard_break = style;
+    checkmark_fmt::fmt_markdown(&utils::create_dummy_md_file(source), &config)
+        .unwrap()
+        .content
+}
+
+#[test]
+fn backslash_break() {
+    utils::assert_unchanged_after_formatting("Line one\\\nLine two\n");
+}
+
+#[test]
+fn spaces_break() {
+    utils::assert_unchanged_after_formatting("Line one  \nLine two\n");
+}
+
+#[test]
+fn break_in_list() {
+    utils::assert_unchanged_after_formatting("- Item\\\n  continued\n- Item\n");
+    utils::assert_unchanged_after_formatting("1. Item\\\n   continued\n2. Item\n");
+}
+
+#[test]
+fn break_in_block_quote() {
+    utils::assert_unchanged_after_formatting("> Quote\\\n> continued\n");
+}
+
+#[test]
+fn consistent_with_first_break() {
+    utils::assert_changed_after_formatting(
+        "Line one  \nLine two\\\nLine three\n",
+        "Line one  \nLine two  \nLine three\n",
+    );
+}
+
+#[test]
+fn configured_style() {
+    assert_eq!(
+        "Line one\\\nLine two\n",
+        fmt_with_style("Line one  \nLine two\n", common::HardBreakStyle::Backslash)
+    );
+    assert_eq!(
+        "Line one  \nLine two\n",
+        fmt_with_style("Line one\\\nLine two\n", common::HardBreakStyle::Spaces)
+    );
+}
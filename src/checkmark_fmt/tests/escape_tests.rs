@@ -0,0 +1,101 @@
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+mod utils;
+
+#[test]
+fn not_a_syntax_is_not_escaped() {
+    utils::assert_unchanged_after_formatting("a < b\n");
+    utils::assert_unchanged_after_formatting("x*y\n");
+    utils::assert_unchanged_after_formatting("a | b\n");
+    utils::assert_unchanged_after_formatting("snake_case\n");
+    utils::assert_unchanged_after_formatting("C:\\Users\n");
+}
+
+#[test]
+fn unnecessary_escapes_are_removed() {
+    utils::assert_changed_after_formatting("a \\< b\n", "a < b\n");
+    utils::assert_changed_after_formatting("x\\*y\n", "x*y\n");
+    utils::assert_changed_after_formatting("2 \\* 3 \\* 4\n", "2 * 3 * 4\n");
+    utils::assert_changed_after_formatting("AT\\&T\n", "AT&T\n");
+}
+
+#[test]
+fn escaped_text_is_not_escaped_twice() {
+    utils::assert_changed_after_formatting("Escaped \\\\ backslash\n", "Escaped \\ backslash\n");
+    // Single "*" after the backslash can't be an emphasis
+    utils::assert_changed_after_formatting(
+        "Escaped \\\\\\* asterisk\n",
+        "Escaped \\\\* asterisk\n",
+    );
+}
+
+#[test]
+fn syntax_stays_escaped() {
+    utils::assert_unchanged_after_formatting("\\<br>\n");
+    utils::assert_unchanged_after_formatting("\\*not emphasis\\*\n");
+    utils::assert_unchanged_after_formatting("\\_not emphasis\\_\n");
+    utils::assert_unchanged_after_formatting("\\[not a link\\](url)\n");
+    utils::assert_unchanged_after_formatting("\\`not code`\n");
+    utils::assert_unchanged_after_formatting("\\&amp;\n");
+}
+
+#[test]
+fn code_span_delimiters_in_different_nodes_stay_escaped() {
+    // Only opening delimiters are escaped, nothing follows the closing ones to pair with
+    utils::assert_changed_after_formatting("a \\`b *c* d\\` e\n", "a \\`b *c* d` e\n");
+    utils::assert_changed_after_formatting("a \\~b *c* d\\~ e\n", "a \\~b *c* d~ e\n");
+    utils::assert_unchanged_after_formatting("a \\`b `code`\n");
+    utils::assert_unchanged_after_formatting("Single ` backtick and *emphasis*\n");
+}
+
+#[test]
+fn unmatched_code_span_and_strikethrough_delimiters_are_not_escaped() {
+    // Closing run of a different length
+    utils::assert_unchanged_after_formatting("a `` b ` c\n");
+    // Closing tilde can't close anything at the line start
+    utils::assert_unchanged_after_formatting("H~2\n~newline-O\n");
+    // List items are different blocks, even without a blank line between them
+    utils::assert_unchanged_after_formatting("- `one\n- two`\n");
+}
+
+#[test]
+fn all_special_characters_are_escaped_when_minimal_escaping_changes_meaning() {
+    // Emphasis delimiters in different text nodes would pair if not escaped,
+    // so the whole document is escaped the old way, including "<"
+    utils::assert_changed_after_formatting(
+        "a \\*b **c** d\\* e, x < y\n",
+        "a \\*b **c** d\\* e, x \\< y\n",
+    );
+}
+
+#[test]
+fn block_syntax_stays_escaped_at_line_start() {
+    utils::assert_unchanged_after_formatting("\\# Not a heading\n");
+    utils::assert_unchanged_after_formatting("\\- Not a list\n");
+    utils::assert_unchanged_after_formatting("1\\. Not a list\n");
+    utils::assert_unchanged_after_formatting("\\> Not a quote\n");
+    utils::assert_unchanged_after_formatting("- \\# Not a heading in a list\n");
+    // Only at the line start
+    utils::assert_changed_after_formatting("Not a \\# heading\n", "Not a # heading\n");
+}
+
+#[test]
+fn round_trip() {
+    let file = utils::create_dummy_md_file(
+        r#"# Escaping
+
+a < b and x*y with a | and snake_case
+
+\<br> and \*not emphasis\*
+
+\# Not a heading
+
+1\. Not a list
+"#,
+    );
+    assert_eq!(
+        Vec::<common::CheckIssue>::new(),
+        checkmark_fmt::verify(&file, &common::Config::default())
+    );
+}
@@ -23,6 +23,7 @@ fn heading_atx_trailing_space_removed() {
             &utils::create_dummy_md_file("#        This is an H1\n"),
             &common::Config::default()
         )
+        .unwrap()
         .content
     );
     assert_eq!(
@@ -31,6 +32,7 @@ fn heading_atx_trailing_space_removed() {
             &utils::create_dummy_md_file("##        This is an H2\n"),
             &common::Config::default()
         )
+        .unwrap()
         .content
     );
 }
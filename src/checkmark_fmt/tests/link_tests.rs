@@ -17,7 +17,7 @@ fn normal_link_rendered() {
 #[test]
 fn auto_links() {
     utils::assert_changed_after_formatting("https://github.com", "<https://github.com>\n");
-    utils::assert_changed_after_formatting("someone@some.where", "<mailto:someone@some.where>\n");
+    utils::assert_changed_after_formatting("someone@some.where", "<someone@some.where>\n");
 }
 
 /// Footnotes
@@ -0,0 +1,38 @@
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+fn create_mdx_file(content: &str) -> common::MarkDownFile {
+    common::MarkDownFile {
+        path: String::from("this/is/a/dummy/path/to/a/file.mdx"),
+        content: String::from(content),
+        issues: vec![],
+    }
+}
+
+#[test]
+fn mdx_nodes_are_kept_as_is() {
+    let original = create_mdx_file(
+        r#"import { Chart } from './chart'
+
+# Title
+
+<Chart year={2024} />
+
+Some {props.value} text with <Badge>new</Badge>.
+"#,
+    );
+    let formatted = checkmark_fmt::fmt_markdown(&original, &common::Config::default()).unwrap();
+    assert_eq!(&original.content, &formatted.content);
+}
+
+#[test]
+fn unparsable_file_is_an_error() {
+    let original = create_mdx_file("# Title\n\n<Chart year={2024}\n");
+    let err = checkmark_fmt::fmt_markdown(&original, &common::Config::default()).unwrap_err();
+    assert_eq!(err.path, original.path);
+
+    let issues = checkmark_fmt::check_md_format(&original, &common::Config::default());
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].severity, common::IssueSeverity::Bug);
+    assert!(issues[0].message.starts_with("Unable to format the file"));
+}
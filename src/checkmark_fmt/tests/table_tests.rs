@@ -23,15 +23,21 @@ fn table_auto_aligned() {
 
 #[test]
 fn table_with_special_symbols() {
-    // Here second row looks like miss-aligned because two "\\"
-    // are just to escape the first one
-    utils::assert_unchanged_after_formatting(
+    // ">" is not a block quote inside of a table, it doesn't have to be escaped
+    utils::assert_changed_after_formatting(
         "#Table
 
 | Syntax | Description |
 | ------ | ----------: |
 | Header | Title       |
 | -\\>    | Text        |
+",
+        "#Table
+
+| Syntax | Description |
+| ------ | ----------: |
+| Header | Title       |
+| ->     | Text        |
 ",
     );
 }
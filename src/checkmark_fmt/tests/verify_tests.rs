@@ -0,0 +1,63 @@
+#[cfg(test)]
+use pretty_assertions::assert_eq;
+
+mod utils;
+
+#[test]
+fn default_code_block_language_keeps_meaning() {
+    // Formatter sets the language, it shall not be considered as a change of the meaning
+    utils::assert_changed_after_formatting("```\ncode\n```\n", "```text\ncode\n```\n");
+}
+
+#[test]
+fn verify_document_variants() {
+    let file = utils::create_dummy_md_file(
+        r#"# Verify
+
+Paragraph with **strong**, _emphasis_ and `code`.\
+Second line after a hard break.
+
+- Item
+- Item with a [link](https://example.com "title")
+"#,
+    );
+    assert_eq!(
+        Vec::<common::CheckIssue>::new(),
+        checkmark_fmt::verify(&file, &common::Config::default())
+    );
+}
+
+/// Meaning of the formatted document compared to the original one
+fn semantic_difference(original: &str, formatted: &str) -> Option<String> {
+    checkmark_fmt::semantic_difference(
+        &utils::create_dummy_md_file(original),
+        &utils::create_dummy_md_file(formatted),
+        &common::Config::default(),
+    )
+}
+
+#[test]
+fn lost_hard_break_changes_meaning() {
+    assert_eq!(
+        Some(String::from(
+            "formatting changes the meaning of the document"
+        )),
+        semantic_difference("First line\\\nSecond line\n", "First line\nSecond line\n")
+    );
+}
+
+#[test]
+fn emphasis_turned_into_text_changes_meaning() {
+    assert!(semantic_difference("Some *emphasis*\n", "Some \\*emphasis\\*\n").is_some());
+}
+
+#[test]
+fn whitespace_at_text_edges_is_kept() {
+    assert!(semantic_difference("a *b*\n", "a*b*\n").is_some());
+}
+
+#[test]
+fn whitespace_runs_are_collapsed() {
+    assert_eq!(None, semantic_difference("a  *b*\n", "a *b*\n"));
+    assert_eq!(None, semantic_difference("a  b\n", "a b\n"));
+}
@@ -18,3 +18,18 @@ And here is a text
 "#,
     );
 }
+
+#[test]
+fn toml() {
+    utils::assert_unchanged_after_formatting(
+        r#"+++
+title = "Your document's title"
+keywords = ["A keyword", "Another keyword"]
++++
+
+## Section
+
+And here is a text
+"#,
+    );
+}
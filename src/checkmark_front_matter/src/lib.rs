@@ -0,0 +1,38 @@
+mod schema;
+mod stale;
+
+pub use schema::{create_validator, invalid_schema, validate, SchemaValidator};
+pub use stale::check_staleness;
+
+use common::{CheckIssue, CheckIssueBuilder, IssueCategory, IssueSeverity, MarkDownFile};
+use markdown::unist::Position;
+
+/// Issue of the front matter located at the position of the file
+fn issue(
+    category: IssueCategory,
+    severity: IssueSeverity,
+    file: &MarkDownFile,
+    position: &Position,
+    message: String,
+    fixes: Vec<String>,
+) -> CheckIssue {
+    CheckIssueBuilder::default()
+        .set_category(category)
+        .set_severity(severity)
+        .set_file_path(file.path.clone())
+        .set_row_num_start(position.start.line)
+        .set_row_num_end(position.end.line)
+        .set_col_num_start(position.start.column)
+        .set_col_num_end(position.end.column)
+        .set_offset_start(position.start.offset)
+        .set_offset_end(position.end.offset)
+        .set_message(message)
+        .set_fixes(fixes)
+        .build()
+}
+
+/// Position of the first line of the file, where front matter is expected
+fn first_line(file: &MarkDownFile) -> Position {
+    let length = file.content.lines().next().unwrap_or_default().len();
+    Position::new(1, 1, 0, 1, length + 1, length)
+}
@@ -0,0 +1,207 @@
+use crate::{first_line, issue};
+use common::front_matter::FrontMatter;
+use common::{CheckIssue, IssueCategory, IssueSeverity, MarkDownFile};
+
+/// Compiled JSON Schema that front matter of documents must match, see `create_validator()`
+pub struct SchemaValidator {
+    /// Where the schema has been read from
+    path: String,
+    validator: jsonschema::Validator,
+}
+
+/// Read and compile the JSON Schema from the config, None when there is no schema.
+/// Formats, i.e. `"format": "date"`, are validated too
+pub fn create_validator(config: &common::Config) -> Result<Option<SchemaValidator>, String> {
+    let Some(path) = &config.front_matter.schema else {
+        return Ok(None);
+    };
+    log::debug!("Reading front matter schema from {:#?}", &path);
+    let schema = std::fs::read_to_string(path)
+        .map_err(|err| format!("Unable to read front matter schema {}: {}", &path, err))?;
+    let schema = serde_json::from_str::<serde_json::Value>(&schema)
+        .map_err(|err| format!("Front matter schema {} is not a valid JSON: {}", &path, err))?;
+    let validator = jsonschema::options()
+        .should_validate_formats(true)
+        .build(&schema)
+        .map_err(|err| format!("Front matter schema {} is invalid: {}", &path, err))?;
+    Ok(Some(SchemaValidator {
+        path: path.clone(),
+        validator,
+    }))
+}
+
+/// Issue of the file whose schema can't be read or compiled, so its front matter can't be validated
+pub fn invalid_schema(file: &MarkDownFile, message: &str) -> CheckIssue {
+    issue(
+        IssueCategory::FrontMatter,
+        IssueSeverity::Error,
+        file,
+        &first_line(file),
+        message.to_string(),
+        vec![
+            "💡 Fix the \"schema\" option in the [front_matter] section of the config".to_string(),
+        ],
+    )
+}
+
+/// Validate front matter of the file against the schema.
+/// Document without front matter is validated as if it had an empty one,
+/// so it's reported when the schema requires any key
+pub fn validate(validator: &SchemaValidator, file: &MarkDownFile) -> Vec<CheckIssue> {
+    let fixes = |fix: String| {
+        vec![
+            "🧠 Tools that consume documents, i.e. docs portals, rely on their front matter"
+                .to_string(),
+            fix,
+        ]
+    };
+    let front_matter = match FrontMatter::from_content(&file.content) {
+        Ok(front_matter) => front_matter,
+        Err(message) => {
+            return vec![issue(
+                IssueCategory::FrontMatter,
+                IssueSeverity::Error,
+                file,
+                &first_line(file),
+                format!("Front matter can't be parsed: {}", message),
+                fixes("💡 Fix the syntax of the front matter".to_string()),
+            )]
+        }
+    };
+    let empty = serde_json::Value::Object(serde_json::Map::new());
+    let value = front_matter
+        .as_ref()
+        .map(|front_matter| &front_matter.value)
+        .unwrap_or(&empty);
+    validator
+        .validator
+        .iter_errors(value)
+        .map(|error| {
+            let path = error.instance_path.to_string();
+            // Point to the key the error is about, otherwise to the whole front matter
+            let key = path.trim_start_matches('/').split('/').next().unwrap_or("");
+            let position = match &front_matter {
+                Some(front_matter) => Some(key)
+                    .filter(|key| !key.is_empty())
+                    .and_then(|key| front_matter.key_position(key, &file.content))
+                    .unwrap_or_else(|| front_matter.position.clone()),
+                None => first_line(file),
+            };
+            let message = match path.is_empty() {
+                true => format!("Front matter: {}", error),
+                false => format!("Front matter {}: {}", path, error),
+            };
+            issue(
+                IssueCategory::FrontMatter,
+                IssueSeverity::Error,
+                file,
+                &position,
+                message,
+                fixes(format!(
+                    "💡 Front matter must match the schema {}",
+                    &validator.path
+                )),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const SCHEMA: &str = r#"{
+        "type": "object",
+        "required": ["title", "owner", "last_reviewed"],
+        "properties": {
+            "title": { "type": "string" },
+            "owner": { "type": "string" },
+            "last_reviewed": { "type": "string", "format": "date" },
+            "status": { "enum": ["draft", "published", "deprecated"] }
+        }
+    }"#;
+
+    fn validator(name: &str) -> SchemaValidator {
+        let path = std::env::temp_dir().join(format!("checkmark_front_matter_{name}.json"));
+        std::fs::write(&path, SCHEMA).unwrap();
+        let mut config = common::Config::default();
+        config.front_matter.schema = Some(path.display().to_string());
+        create_validator(&config).unwrap().unwrap()
+    }
+
+    fn file(content: &str) -> MarkDownFile {
+        MarkDownFile {
+            path: "guide.md".to_string(),
+            content: content.to_string(),
+            issues: vec![],
+        }
+    }
+
+    /// Line and message of every issue, sorted by line
+    fn messages(issues: &[CheckIssue]) -> Vec<(usize, String)> {
+        let mut messages = issues
+            .iter()
+            .map(|issue| (issue.row_num_start, issue.message.clone()))
+            .collect::<Vec<_>>();
+        messages.sort();
+        messages
+    }
+
+    #[test]
+    fn valid_front_matter() {
+        let validator = validator("valid");
+        let content = "---\ntitle: Guide\nowner: docs-team\nlast_reviewed: 2024-01-31\nstatus: draft\n---\n# Guide\n";
+        assert_eq!(validate(&validator, &file(content)), vec![]);
+        let content = "+++\ntitle = \"Guide\"\nowner = \"docs-team\"\nlast_reviewed = 2024-01-31\n+++\n# Guide\n";
+        assert_eq!(validate(&validator, &file(content)), vec![]);
+    }
+
+    #[test]
+    fn invalid_front_matter() {
+        let validator = validator("invalid");
+        let content =
+            "---\ntitle: Guide\nlast_reviewed: 31.01.2024\nstatus: drafty\n---\n# Guide\n";
+        let issues = validate(&validator, &file(content));
+        assert_eq!(
+            messages(&issues),
+            vec![
+                (
+                    1,
+                    "Front matter: \"owner\" is a required property".to_string()
+                ),
+                (
+                    3,
+                    "Front matter /last_reviewed: \"31.01.2024\" is not a \"date\"".to_string()
+                ),
+                (
+                    4,
+                    "Front matter /status: \"drafty\" is not one of [\"draft\",\"published\",\"deprecated\"]"
+                        .to_string()
+                ),
+            ]
+        );
+        let last_reviewed = issues
+            .iter()
+            .find(|issue| issue.row_num_start == 3)
+            .unwrap();
+        assert_eq!(last_reviewed.col_num_end, 26);
+        assert!(issues
+            .iter()
+            .all(|issue| issue.category == IssueCategory::FrontMatter));
+    }
+
+    #[test]
+    fn missing_front_matter() {
+        let validator = validator("missing");
+        let issues = validate(&validator, &file("# Guide\n"));
+        assert_eq!(issues.len(), 3);
+        assert!(issues.iter().all(|issue| issue.row_num_start == 1));
+
+        let issues = validate(&validator, &file("---\ntitle: [\n---\n# Guide\n"));
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0]
+            .message
+            .starts_with("Front matter can't be parsed"));
+    }
+}
@@ -0,0 +1,162 @@
+use crate::{first_line, issue};
+use chrono::NaiveDate;
+use common::front_matter::FrontMatter;
+use common::{CheckIssue, IssueCategory, IssueSeverity, MarkDownFile};
+
+/// Keys of the front matter with the date the document has been reviewed at, in order of preference
+const REVIEW_DATE_KEYS: [&str; 2] = ["last_reviewed", "updated"];
+
+/// Date at the beginning of the value, i.e. "2024-01-31" or "2024-01-31T10:00:00Z"
+fn parse_date(value: &serde_json::Value) -> Option<NaiveDate> {
+    let value = value.as_str()?;
+    NaiveDate::parse_from_str(value.get(..10).unwrap_or(value), "%Y-%m-%d").ok()
+}
+
+/// Report the file when it has not been reviewed for more than `max_age_days` of the config.
+/// Review date is taken from the front matter, when there is none - from the last commit
+/// that changed the file(seconds since the Unix epoch). Files without both are never stale
+pub fn check_staleness(
+    file: &MarkDownFile,
+    config: &common::Config,
+    last_commit_time: Option<i64>,
+    today: NaiveDate,
+) -> Vec<CheckIssue> {
+    let front_matter = FrontMatter::from_content(&file.content).ok().flatten();
+    let review_date = front_matter.as_ref().and_then(|front_matter| {
+        REVIEW_DATE_KEYS.iter().find_map(|key| {
+            let value = front_matter.value.get(key)?;
+            match parse_date(value) {
+                Some(date) => Some((*key, date)),
+                None => {
+                    log::warn!(
+                        "Ignoring \"{}\" of {}: {} is not a date like 2024-01-31",
+                        key,
+                        &file.path,
+                        value
+                    );
+                    None
+                }
+            }
+        })
+    });
+    let (date, position, reason, fix) = match review_date {
+        Some((key, date)) => (
+            date,
+            front_matter
+                .as_ref()
+                .and_then(|front_matter| front_matter.key_position(key, &file.content))
+                .unwrap_or_else(|| first_line(file)),
+            format!("reviewed on {} according to \"{}\"", date, key),
+            format!(
+                "💡 Review the document and set \"{}\" in its front matter to the date of the review",
+                key
+            ),
+        ),
+        None => {
+            let Some(date) = last_commit_time
+                .and_then(|time| chrono::DateTime::from_timestamp(time, 0))
+                .map(|time| time.date_naive())
+            else {
+                return vec![];
+            };
+            (
+                date,
+                first_line(file),
+                format!("changed on {} according to git", date),
+                "💡 Review the document and add \"last_reviewed\" with the date of the review to its front matter".to_string(),
+            )
+        }
+    };
+    let age = (today - date).num_days();
+    if age <= i64::from(config.stale.max_age_days) {
+        return vec![];
+    }
+    vec![issue(
+        IssueCategory::Stale,
+        IssueSeverity::Warning,
+        file,
+        &position,
+        format!(
+            "Document is due for review: last {}, {} days ago(more than {})",
+            reason, age, config.stale.max_age_days
+        ),
+        vec![
+            "🧠 Outdated documentation misleads readers, review it from time to time".to_string(),
+            fix,
+        ],
+    )]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn file(content: &str) -> MarkDownFile {
+        MarkDownFile {
+            path: "guide.md".to_string(),
+            content: content.to_string(),
+            issues: vec![],
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 6, 1).unwrap()
+    }
+
+    /// 2023-01-01T00:00:00Z
+    const LAST_YEAR: i64 = 1_672_531_200;
+
+    #[test]
+    fn stale_by_front_matter() {
+        let config = common::Config::default();
+        let content = "---\ntitle: Guide\nlast_reviewed: 2023-05-01\n---\n# Guide\n";
+        let issues = check_staleness(&file(content), &config, None, today());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].category, IssueCategory::Stale);
+        assert_eq!(
+            issues[0].message,
+            "Document is due for review: last reviewed on 2023-05-01 according to \"last_reviewed\", 397 days ago(more than 365)"
+        );
+        assert_eq!(issues[0].row_num_start, 3);
+
+        let content = "+++\nupdated = 2023-05-01T10:00:00Z\n+++\n# Guide\n";
+        let issues = check_staleness(&file(content), &config, None, today());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].row_num_start, 2);
+    }
+
+    #[test]
+    fn recently_reviewed() {
+        let config = common::Config::default();
+        // Review date wins over the last commit
+        let content = "---\nlast_reviewed: 2024-01-31\n---\n# Guide\n";
+        assert_eq!(
+            check_staleness(&file(content), &config, Some(LAST_YEAR), today()),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn stale_by_last_commit() {
+        let mut config = common::Config::default();
+        let issues = check_staleness(&file("# Guide\n"), &config, Some(LAST_YEAR), today());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(
+            issues[0].message,
+            "Document is due for review: last changed on 2023-01-01 according to git, 517 days ago(more than 365)"
+        );
+        assert_eq!(issues[0].row_num_start, 1);
+
+        config.stale.max_age_days = 600;
+        assert_eq!(
+            check_staleness(&file("# Guide\n"), &config, Some(LAST_YEAR), today()),
+            vec![]
+        );
+        // Never committed
+        assert_eq!(
+            check_staleness(&file("# Guide\n"), &config, None, today()),
+            vec![]
+        );
+    }
+}
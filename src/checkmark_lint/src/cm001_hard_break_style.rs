@@ -0,0 +1,171 @@
+use crate::violation::{Violation, ViolationBuilder};
+use common::MarkDownFile;
+use markdown::mdast::Break;
+use markdown::unist::Position;
+
+fn violation_builder() -> ViolationBuilder {
+    ViolationBuilder::default()
+        .code("CM001")
+        .doc_link("https://github.com/vvvar/checkmark/tree/main#lint")
+        .rationale("Consistent formatting makes it easier to understand a document. Trailing spaces are invisible in most editors, so it is hard to tell a hard break from a soft one when styles are mixed")
+        .is_fmt_fixable(true)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HardBreakStyle {
+    /// Same as first hard break in file
+    Consistent,
+    /// "\" before the line ending
+    Backslash,
+    /// Two or more spaces before the line ending
+    Spaces,
+}
+
+impl HardBreakStyle {
+    pub fn as_string(&self) -> String {
+        match self {
+            HardBreakStyle::Consistent => "",
+            HardBreakStyle::Backslash => "backslash",
+            HardBreakStyle::Spaces => "spaces",
+        }
+        .to_string()
+    }
+}
+
+/// Position of the break marker(trailing spaces or backslash) only,
+/// break node itself ends at the start of the next line
+fn marker_position(b: &Break, source: &str) -> Option<Position> {
+    let start = &b.position.as_ref()?.start;
+    let marker_len = source
+        .get(start.offset..)?
+        .chars()
+        .take_while(|c| !matches!(c, '\r' | '\n'))
+        .count();
+    Some(Position::new(
+        start.line,
+        start.column,
+        start.offset,
+        start.line,
+        start.column + marker_len,
+        start.offset + marker_len,
+    ))
+}
+
+pub fn cm001_hard_break_style(file: &MarkDownFile, style: &HardBreakStyle) -> Vec<Violation> {
+    log::debug!("[CM001] File: {:#?}", &file.path);
+
+    let ast = common::ast::parse(&file.content).unwrap();
+
+    let breaks = common::ast::BfsIterator::from(&ast)
+        .filter_map(|n| common::ast::try_cast_to_break(n))
+        .collect::<Vec<_>>();
+
+    // Get style of the hard break
+    let get_break_style = |b: &Break, source: &str| -> HardBreakStyle {
+        let offset_start = b.position.as_ref().unwrap().start.offset;
+        if source.get(offset_start..).unwrap_or("").starts_with('\\') {
+            HardBreakStyle::Backslash
+        } else {
+            HardBreakStyle::Spaces
+        }
+    };
+
+    let preferred_style = match style {
+        HardBreakStyle::Consistent => {
+            if let Some(b) = breaks.first() {
+                get_break_style(b, &file.content)
+            } else {
+                HardBreakStyle::Backslash
+            }
+        }
+        HardBreakStyle::Backslash => HardBreakStyle::Backslash,
+        HardBreakStyle::Spaces => HardBreakStyle::Spaces,
+    };
+
+    breaks
+        .iter()
+        .filter(|b| get_break_style(b, &file.content).ne(&preferred_style))
+        .map(|b| {
+            let mut violation = violation_builder()
+                .position(&marker_position(b, &file.content))
+                .message(&format!(
+                    "Wrong hard break style. Expected {:#?}, got {:#?}",
+                    &preferred_style.as_string(),
+                    get_break_style(b, &file.content).as_string()
+                ));
+            if style.eq(&HardBreakStyle::Consistent) {
+                violation = violation.push_fix(&format!(
+                    "Hard break style is configured to be consistent across the document. First hard break in document uses {:#?}, but this one uses {:#?}",
+                    &preferred_style.as_string(),
+                    get_break_style(b, &file.content).as_string(),
+                ));
+            } else {
+                violation = violation.push_fix(&format!(
+                    "Hard break style is configured to use {:#?}",
+                    &preferred_style.as_string(),
+                ));
+            }
+            violation.build()
+        })
+        .collect::<Vec<Violation>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn file() -> MarkDownFile {
+        MarkDownFile {
+            path: String::from("this/is/a/dummy/path/to/a/file.md"),
+            content: "Line one  \nLine two\\\nLine three\n".to_string(),
+            issues: vec![],
+        }
+    }
+
+    #[test]
+    fn cm001_consistent() {
+        assert_eq!(
+            vec![violation_builder()
+                .message("Wrong hard break style. Expected \"spaces\", got \"backslash\"")
+                .position(&Some(Position::new(2, 9, 19, 2, 10, 20)))
+                .build()],
+            cm001_hard_break_style(&file(), &HardBreakStyle::Consistent)
+        );
+    }
+
+    #[test]
+    fn cm001_backslash() {
+        assert_eq!(
+            vec![violation_builder()
+                .message("Wrong hard break style. Expected \"backslash\", got \"spaces\"")
+                .position(&Some(Position::new(1, 9, 8, 1, 11, 10)))
+                .build()],
+            cm001_hard_break_style(&file(), &HardBreakStyle::Backslash)
+        );
+    }
+
+    #[test]
+    fn cm001_spaces() {
+        assert_eq!(
+            vec![violation_builder()
+                .message("Wrong hard break style. Expected \"spaces\", got \"backslash\"")
+                .position(&Some(Position::new(2, 9, 19, 2, 10, 20)))
+                .build()],
+            cm001_hard_break_style(&file(), &HardBreakStyle::Spaces)
+        );
+    }
+
+    #[test]
+    fn cm001_no_breaks() {
+        let file = MarkDownFile {
+            path: String::from("this/is/a/dummy/path/to/a/file.md"),
+            content: "Line one\nLine two\n".to_string(),
+            issues: vec![],
+        };
+        assert_eq!(
+            Vec::<Violation>::new(),
+            cm001_hard_break_style(&file, &HardBreakStyle::Consistent)
+        );
+    }
+}
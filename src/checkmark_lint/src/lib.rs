@@ -1,3 +1,4 @@
+mod cm001_hard_break_style;
 mod md001_heading_level_should_increment_by_one_level_at_time;
 mod md003_heading_style;
 mod md004_unordered_list_style;
@@ -27,6 +28,7 @@ mod md046_code_block_style;
 mod md051_link_fragments_should_be_valid;
 mod violation;
 
+use cm001_hard_break_style::*;
 use colored::Colorize;
 use common::{CheckIssue, Config, MarkDownFile};
 use md001_heading_level_should_increment_by_one_level_at_time::*;
@@ -109,9 +111,24 @@ pub fn lint(file: &MarkDownFile, config: &Config) -> Vec<CheckIssue> {
         md033_inline_html(file, &config.linter.md033_allowed_html_tags),
         md046_code_block_style(file, &CodeBlockStyle::Consistent),
         md051_link_fragments_should_be_valid(file),
+        cm001_hard_break_style(
+            file,
+            &match config.style.hard_break {
+                common::HardBreakStyle::Consistent => HardBreakStyle::Consistent,
+                common::HardBreakStyle::Backslash => HardBreakStyle::Backslash,
+                common::HardBreakStyle::Spaces => HardBreakStyle::Spaces,
+            },
+        ),
     ]
     .into_par_iter()
     .flatten()
+    .filter(|violation| {
+        !config
+            .linter
+            .disabled_rules
+            .iter()
+            .any(|code| code.eq_ignore_ascii_case(&violation.code))
+    })
     .map(|violation| {
         let mut issue = common::CheckIssueBuilder::default()
             .set_category(common::IssueCategory::Linting)
@@ -13,15 +13,23 @@ fn violation_builder() -> ViolationBuilder {
 
 pub fn md009_trailing_spaces(file: &MarkDownFile) -> Vec<Violation> {
     log::debug!("[MD009] File: {:#?}", &file.path);
+    // Trailing spaces that form a hard break are intentional
+    let ast = common::ast::parse(&file.content).unwrap();
+    let hard_break_lines = common::ast::BfsIterator::from(&ast)
+        .filter_map(|n| common::ast::try_cast_to_break(n))
+        .filter_map(|b| b.position.as_ref())
+        .filter(|p| !file.content[p.start.offset..].starts_with('\\'))
+        .map(|p| p.start.line)
+        .collect::<Vec<usize>>();
     let mut is_code_block = false;
     file.content
         .lines()
         .enumerate()
-        .filter(|(_, line)| {
+        .filter(|(i, line)| {
             if line.contains("```") {
                 is_code_block = !is_code_block;
             }
-            if is_code_block {
+            if is_code_block || hard_break_lines.contains(&(i + 1)) {
                 false
             } else {
                 line.ends_with(' ')
@@ -92,4 +100,14 @@ This is a fenced code block
             md009_trailing_spaces(&file)
         );
     }
+
+    #[test]
+    pub fn md009_hard_break() {
+        let file = common::MarkDownFile {
+            path: String::from("this/is/a/dummy/path/to/a/file.md"),
+            content: "Hard  \nbreak\n".to_string(),
+            issues: vec![],
+        };
+        assert_eq!(Vec::<Violation>::new(), md009_trailing_spaces(&file));
+    }
 }
@@ -45,7 +45,7 @@ pub fn md033_inline_html(file: &MarkDownFile, allowed_tags: &Vec<String>) -> Vec
                 .position(&node.position)
                 .push_fix("If your intention was show this HTML tag as a text, consider escaping it with \"\\\". For example: \"\\<br\\>\"")
                 .push_fix("If it's not the case, consider using Markdown instead of HTML")
-                .push_fix("If this HTML tag is needed, then consider adding a name of this element to the list of allowed tags. Use \"allowed_html_tags\" option from the \"[lint]\" section in the config file")
+                .push_fix("If this HTML tag is needed, then consider adding a name of this element to the list of allowed tags. Use \"md033_allowed_html_tags\" option from the \"[linter]\" section in the config file or disable MD033 in the front matter of the document")
                 .build()
         })
         .collect::<Vec<Violation>>()
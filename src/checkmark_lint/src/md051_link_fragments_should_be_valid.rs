@@ -28,26 +28,6 @@ fn extract_headings(ast: &Node) -> Vec<&Heading> {
         .collect::<Vec<&Heading>>()
 }
 
-/// Takes heading and returns fragment link of it.
-/// Link element that want to jump to this header
-/// should use this fragment.
-fn heading_to_fragment(heading: &Heading) -> String {
-    let mut text = "".to_string();
-    for node in &heading.children {
-        if let Node::Text(t) = node {
-            text = format!("{}{}", &text, &t.value);
-        }
-    }
-    text = format!(
-        "#{}",
-        &text
-            .to_lowercase()
-            .replace([',', '.', '+', '&'], "")
-            .replace(' ', "-")
-    );
-    text
-}
-
 /// Get all HTML links(<a/>).
 /// At least one of them shall contain an anchor.
 fn extract_html_elements(ast: &Node) -> Vec<scraper::Node> {
@@ -73,7 +53,7 @@ fn find_violations(
     let does_fragment_points_to_header = |anchor: &Link| {
         headings
             .iter()
-            .any(|heading| anchor.url.eq(&heading_to_fragment(heading)))
+            .any(|heading| anchor.url.eq(&common::ast::heading_to_fragment(heading)))
     };
     // Does anchor points to any other anchor in HTML <a id="#anchor"/>?
     let does_fragment_points_to_html = |link: &Link| {
@@ -0,0 +1,58 @@
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// Supported archive formats, detected by the file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Format {
+    Zip,
+    Tar,
+    TarGz,
+}
+
+impl Format {
+    pub(crate) fn from_path(path: &str) -> Option<Self> {
+        let path = path.to_lowercase();
+        if path.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if path.ends_with(".tar") {
+            Some(Self::Tar)
+        } else if path.ends_with(".tar.gz") || path.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else {
+            None
+        }
+    }
+}
+
+/// Dir where the archive is extracted, unique per archive
+pub(crate) fn extract_dir(archive: &Path) -> std::io::Result<PathBuf> {
+    let archive = dunce::canonicalize(archive)?;
+    let hash = format!("{:x}", Sha256::digest(archive.to_string_lossy().as_bytes()));
+    let name = archive
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("archive");
+    Ok(std::env::temp_dir()
+        .join("checkmark")
+        .join("archives")
+        .join(format!("{}-{}", name, &hash[..16])))
+}
+
+/// Extract the archive into a fresh temp dir and return it.
+/// Entries pointing outside of the dir(i.e. "../file.md") fail the extraction
+pub(crate) fn extract(archive: &Path, format: Format) -> std::io::Result<PathBuf> {
+    let dir = extract_dir(archive)?;
+    if dir.exists() {
+        log::debug!("Directory {:#?} already exists, removing", &dir);
+        std::fs::remove_dir_all(&dir)?;
+    }
+    std::fs::create_dir_all(&dir)?;
+    log::debug!("Extracting {:#?} into the {:#?}", &archive, &dir);
+    let file = std::fs::File::open(archive)?;
+    match format {
+        Format::Zip => zip::ZipArchive::new(file)?.extract(&dir)?,
+        Format::Tar => tar::Archive::new(file).unpack(&dir)?,
+        Format::TarGz => tar::Archive::new(flate2::read::GzDecoder::new(file)).unpack(&dir)?,
+    }
+    dunce::canonicalize(dir)
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// Which git changes should be checked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitChanges {
+    /// Changes between a ref(commit, branch or tag) and the working tree,
+    /// including staged and untracked files
+    Since(String),
+    /// Changes staged for the next commit
+    Staged,
+}
+
+/// Compute a diff for requested changes in the repository
+fn diff<'a>(
+    repo: &'a git2::Repository,
+    changes: &GitChanges,
+) -> Result<git2::Diff<'a>, git2::Error> {
+    let mut options = git2::DiffOptions::new();
+    options
+        .context_lines(0)
+        .include_untracked(true)
+        .recurse_untracked_dirs(true)
+        .show_untracked_content(true);
+    match changes {
+        GitChanges::Since(reference) => {
+            let tree = repo.revparse_single(reference)?.peel_to_tree()?;
+            repo.diff_tree_to_workdir_with_index(Some(&tree), Some(&mut options))
+        }
+        GitChanges::Staged => {
+            // There is no HEAD in a fresh repository, everything is new then
+            let tree = match repo.head() {
+                Ok(head) => Some(head.peel_to_tree()?),
+                Err(_) => None,
+            };
+            repo.diff_tree_to_index(tree.as_ref(), None, Some(&mut options))
+        }
+    }
+}
+
+/// Returns absolute paths of files that were added or modified.
+/// Repository is discovered from the provided path(it could be any dir or file inside of it)
+pub fn changed_files(path: &str, changes: &GitChanges) -> Result<Vec<String>, git2::Error> {
+    log::debug!("Looking for {:#?} in {:#?}", &changes, &path);
+    let repo = git2::Repository::discover(path)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| git2::Error::from_str("Bare repositories are not supported"))?
+        .to_path_buf();
+    let mut files = diff(&repo, changes)?
+        .deltas()
+        .filter(|delta| {
+            matches!(
+                delta.status(),
+                git2::Delta::Added
+                    | git2::Delta::Modified
+                    | git2::Delta::Renamed
+                    | git2::Delta::Copied
+                    | git2::Delta::Typechange
+                    | git2::Delta::Untracked
+            )
+        })
+        .filter_map(|delta| delta.new_file().path().map(|path| workdir.join(path)))
+        .filter_map(|path| dunce::canonicalize(path).ok())
+        .map(|path| path.display().to_string())
+        .collect::<Vec<String>>();
+    files.sort();
+    files.dedup();
+    log::debug!("Changed files: {:#?}", &files);
+    Ok(files)
+}
+
+/// Returns line numbers(1-based) that were added or modified in every changed file.
+/// Keys are absolute file paths, consecutive lines are merged into a single range
+pub fn changed_lines(
+    path: &str,
+    changes: &GitChanges,
+) -> Result<HashMap<String, Vec<RangeInclusive<usize>>>, git2::Error> {
+    log::debug!("Looking for lines of {:#?} in {:#?}", &changes, &path);
+    let repo = git2::Repository::discover(path)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| git2::Error::from_str("Bare repositories are not supported"))?
+        .to_path_buf();
+    let mut lines: HashMap<String, Vec<RangeInclusive<usize>>> = HashMap::new();
+    diff(&repo, changes)?.foreach(
+        &mut |_, _| true,
+        None,
+        None,
+        Some(&mut |delta, _, line| {
+            let file_path = delta
+                .new_file()
+                .path()
+                .and_then(|path| dunce::canonicalize(workdir.join(path)).ok());
+            if let (Some(file_path), Some(line_num)) = (file_path, line.new_lineno()) {
+                if line.origin() == '+' {
+                    let line_num = line_num as usize;
+                    let ranges = lines.entry(file_path.display().to_string()).or_default();
+                    match ranges.last_mut() {
+                        Some(last) if *last.end() + 1 == line_num => {
+                            *last = *last.start()..=line_num;
+                        }
+                        _ => ranges.push(line_num..=line_num),
+                    }
+                }
+            }
+            true
+        }),
+    )?;
+    log::debug!("Changed lines: {:#?}", &lines);
+    Ok(lines)
+}
+
+/// Returns time(seconds since the Unix epoch) of the last commit that changed every file.
+/// Keys are absolute file paths, files that have never been committed are omitted.
+/// Repository is discovered from the provided path(it could be any dir or file inside of it)
+pub fn last_commit_times(
+    path: &str,
+    files: &[String],
+) -> Result<HashMap<String, i64>, git2::Error> {
+    log::debug!(
+        "Looking for last commits of {} files in {:#?}",
+        files.len(),
+        &path
+    );
+    let repo = git2::Repository::discover(path)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| git2::Error::from_str("Bare repositories are not supported"))?;
+    let workdir = dunce::canonicalize(workdir).unwrap_or(workdir.to_path_buf());
+    let mut times = HashMap::new();
+    // There is no HEAD in a fresh repository, nothing has been committed then
+    let Ok(head) = repo.head().and_then(|head| head.peel_to_tree()) else {
+        return Ok(times);
+    };
+    // Files that are not in HEAD are either untracked or deleted, they have no last commit
+    let mut pending = files
+        .iter()
+        .filter_map(|file| {
+            let relative_path = std::path::Path::new(file).strip_prefix(&workdir).ok()?;
+            head.get_path(relative_path).ok()?;
+            Some((file.clone(), relative_path.to_path_buf()))
+        })
+        .collect::<Vec<_>>();
+    let mut revwalk = repo.revwalk()?;
+    revwalk.set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::TIME)?;
+    revwalk.push_head()?;
+    for oid in revwalk {
+        if pending.is_empty() {
+            break;
+        }
+        let commit = repo.find_commit(oid?)?;
+        let tree = commit.tree()?;
+        let parent_trees = commit
+            .parents()
+            .map(|parent| parent.tree())
+            .collect::<Result<Vec<_>, _>>()?;
+        let entry_id = |tree: &git2::Tree, path: &std::path::Path| {
+            tree.get_path(path).ok().map(|entry| entry.id())
+        };
+        // File has been changed by the commit when it differs from all parents,
+        // otherwise it came from the parent it's equal to
+        pending.retain(|(file, relative_path)| {
+            let id = entry_id(&tree, relative_path);
+            let changed = id.is_some()
+                && parent_trees
+                    .iter()
+                    .all(|parent| entry_id(parent, relative_path).ne(&id));
+            if changed {
+                times.insert(file.clone(), commit.time().seconds());
+            }
+            !changed
+        });
+    }
+    log::debug!("Last commit times: {:#?}", &times);
+    Ok(times)
+}
@@ -1,4 +1,12 @@
-use auth_git2::GitAuthenticator;
+mod archive;
+mod changes;
+mod remote;
+mod watch;
+
+pub use changes::{changed_files, changed_lines, last_commit_times, GitChanges};
+pub use remote::RemoteRepo;
+pub use watch::{Change, Watcher};
+
 use log::warn;
 use path_slash::PathExt as _;
 use std::borrow::Cow;
@@ -19,73 +27,177 @@ fn tmp_dir(uri: &str) -> std::path::PathBuf {
     path
 }
 
+/// Path that stands for the standard input
+pub const STDIN: &str = "-";
+
+/// Reads a document from stdin. It is reported under the provided file name(if any),
+/// relative names are resolved against the current dir so they look like discovered files
+fn read_stdin(config: &common::GlobalConfig) -> Option<common::MarkDownFile> {
+    let path = match &config.stdin_filename {
+        Some(filename) => match std::env::current_dir() {
+            Ok(current_dir) => current_dir.join(filename).display().to_string(),
+            Err(_) => filename.clone(),
+        },
+        None => STDIN.to_string(),
+    };
+    match std::io::read_to_string(std::io::stdin()) {
+        Ok(content) => Some(common::MarkDownFile {
+            path,
+            content,
+            issues: vec![],
+        }),
+        Err(error) => {
+            warn!("Unable to read Markdown from stdin, error: {}", &error);
+            None
+        }
+    }
+}
+
+/// Name of the file with checkmark-specific ignore rules(gitignore syntax)
+const IGNORE_FILE_NAME: &str = ".checkmarkignore";
+
+/// Build a matcher for the file patterns that shall be discovered
+pub(crate) fn include_matcher(config: &common::GlobalConfig) -> globset::GlobSet {
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in &config.include {
+        match globset::Glob::new(pattern) {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(error) => warn!("Invalid include pattern {:#?}, error: {}", &pattern, &error),
+        }
+    }
+    builder.build().unwrap_or_else(|error| {
+        warn!("Unable to build include patterns, error: {}", &error);
+        globset::GlobSet::empty()
+    })
+}
+
+/// Checks whether file path(relative to the root dir) matches any of include patterns
+pub(crate) fn is_included(matcher: &globset::GlobSet, root: &Path, path: &Path) -> bool {
+    let relative_path = path.strip_prefix(root).unwrap_or(path);
+    match relative_path.to_slash() {
+        Some(unix_style_path) => matcher.is_match(unix_style_path.as_ref()),
+        None => false,
+    }
+}
+
+/// Checks whether the file is ignored by .gitignore, .ignore or .checkmarkignore in any of its parent dirs,
+/// the same way as the discovery does without walking the whole tree.
+/// Rules of deeper dirs win, within the same dir .checkmarkignore wins over .ignore and .ignore over .gitignore
+pub(crate) fn is_ignored(path: &Path) -> bool {
+    let is_dir = path.is_dir();
+    for dir in path.ancestors().skip(1) {
+        for ignore_file_name in [IGNORE_FILE_NAME, ".ignore", ".gitignore"] {
+            let ignore_file = dir.join(ignore_file_name);
+            if !ignore_file.is_file() {
+                continue;
+            }
+            let mut builder = ignore::gitignore::GitignoreBuilder::new(dir);
+            if let Some(error) = builder.add(&ignore_file) {
+                warn!("Unable to read {:#?}, error: {}", &ignore_file, &error);
+            }
+            let gitignore = match builder.build() {
+                Ok(gitignore) => gitignore,
+                Err(error) => {
+                    warn!("Unable to parse {:#?}, error: {}", &ignore_file, &error);
+                    continue;
+                }
+            };
+            match gitignore.matched_path_or_any_parents(path, is_dir) {
+                ignore::Match::Ignore(_) => {
+                    log::debug!("Ignoring {:#?} because of {:#?}", &path, &ignore_file);
+                    return true;
+                }
+                ignore::Match::Whitelist(_) => return false,
+                ignore::Match::None => {}
+            }
+        }
+    }
+    false
+}
+
+/// Recursively finds all Markdown files(matching include patterns) in the dir.
+/// Unless disabled, files ignored by .gitignore, .ignore or .checkmarkignore are skipped
+pub(crate) fn find_markdown_files(root: &Path, config: &common::GlobalConfig) -> Vec<String> {
+    log::debug!("Searching Markdown files in: {:#?}", &root);
+    let matcher = include_matcher(config);
+    let mut walker = ignore::WalkBuilder::new(root);
+    walker
+        .standard_filters(!config.no_ignore)
+        .hidden(false)
+        .require_git(false)
+        .follow_links(true)
+        .filter_entry(|entry| entry.file_name() != ".git")
+        .sort_by_file_name(|a, b| a.cmp(b));
+    if !config.no_ignore {
+        walker.add_custom_ignore_filename(IGNORE_FILE_NAME);
+    }
+    walker
+        .build()
+        .filter_map(|entry| match entry {
+            Ok(entry) => Some(entry),
+            Err(error) => {
+                warn!(
+                    "Unable to read a path while searching Markdown files, error: {}",
+                    &error
+                );
+                None
+            }
+        })
+        .filter(|entry| entry.file_type().map(|t| t.is_file()).unwrap_or(false))
+        .filter(|entry| is_included(&matcher, root, entry.path()))
+        .filter_map(|entry| match dunce::canonicalize(entry.path()) {
+            Ok(path) => Some(path.display().to_string()),
+            Err(error) => {
+                warn!(
+                    "Cannot obtain an absolute path to found file, error: {}",
+                    &error
+                );
+                None
+            }
+        })
+        .collect()
+}
+
 /// Creates a list of markdown files from provided path
 /// Path could be:
 ///     1. path to a file - will just add this file to the list
 ///     2. path to a dir - will lookup all markdown files in this ir
-///     3. remote URL
+///     3. remote URL, either a single file or a git repo(see RemoteRepo for the syntax)
+///     4. path to a .zip, .tar, .tar.gz or .tgz archive - will lookup all markdown files in it
+///     5. "-" - will read a single document from stdin
 pub async fn ls(
     path: &str,
-    exclude: &Vec<String>,
+    config: &common::GlobalConfig,
     tui: &Arc<Mutex<common::tui::CheckProgressTUI>>,
 ) -> Vec<common::MarkDownFile> {
     log::debug!("Listing Markdown files in: {:#?}", &path);
 
+    // Piped document is checked as is, neither include nor exclude patterns apply to it
+    if path.eq(STDIN) {
+        log::debug!("Path is stdin");
+        return read_stdin(config).into_iter().collect();
+    }
+
     let mut input_path = path.to_owned();
 
-    if input_path.ends_with(".git") {
-        log::debug!("Path is a git repo, cloning into tmp dir");
+    if let Some(remote) = RemoteRepo::parse(&input_path) {
+        log::debug!("Path is a git repo, fetching into the cache dir");
         tui.lock()
             .unwrap()
-            .start_spinner(&format!("Cloning git repo {path}"));
-
-        let auth = GitAuthenticator::default();
-        let git_config = git2::Config::open_default().unwrap();
-
-        let mut cb = git2::RemoteCallbacks::new();
-        cb.transfer_progress(|stats| {
-            log::trace!(
-                "transfer_progress callback, stats.indexed_deltas(): {}",
-                &stats.indexed_deltas()
-            );
-            log::trace!(
-                "transfer_progress callback, stats.indexed_objects(): {}",
-                &stats.indexed_objects()
-            );
-            log::trace!(
-                "transfer_progress callback, stats.received_bytes(): {}",
-                &stats.received_bytes()
-            );
-            log::trace!(
-                "transfer_progress callback, stats.received_objects(): {}",
-                &stats.received_objects()
-            );
-            true
-        });
-        cb.credentials(auth.credentials(&git_config));
-
-        let mut co = git2::build::CheckoutBuilder::new();
-        co.progress(|path, cur, total| {
-            if let Some(path) = path {
-                log::trace!("progress callback, path: {}", &path.display());
+            .start_spinner(&format!("Fetching git repo {path}"));
+        match remote.checkout() {
+            Ok(dir) => input_path = dir.display().to_string(),
+            Err(error) => {
+                warn!(
+                    "Unable to fetch git repo {}, error: {}",
+                    &path,
+                    error.message()
+                );
+                return vec![];
             }
-            log::trace!("progress callback, cur: {}", &cur);
-            log::trace!("progress callback, total: {}", &total);
-        });
-
-        let mut fo = git2::FetchOptions::new();
-        fo.remote_callbacks(cb);
-
-        let tmp_dir = tmp_dir(&input_path);
-
-        log::debug!("Cloning into the {:#?}", &tmp_dir);
-        git2::build::RepoBuilder::new()
-            .fetch_options(fo)
-            .with_checkout(co)
-            .clone(&input_path, std::path::Path::new(&tmp_dir))
-            .unwrap();
-        log::debug!("Cloned {:#?} into the {:#?}", &input_path, &tmp_dir);
-        input_path = tmp_dir.to_str().unwrap().to_owned();
+        }
     } else if is_url::is_url(&input_path) {
         log::debug!("Path is a plain URL, downloading as single file into tmp dir");
 
@@ -106,6 +218,18 @@ pub async fn ls(
         std::io::copy(&mut content, &mut file).unwrap();
 
         input_path = tmp_file_path.to_str().unwrap().to_owned();
+    } else if let Some(format) = archive::Format::from_path(&input_path) {
+        log::debug!("Path is an archive, extracting into tmp dir");
+        tui.lock()
+            .unwrap()
+            .start_spinner(&format!("Extracting archive {path}"));
+        match archive::extract(Path::new(&input_path), format) {
+            Ok(dir) => input_path = dir.display().to_string(),
+            Err(error) => {
+                warn!("Unable to extract archive {}, error: {}", &path, &error);
+                return vec![];
+            }
+        }
     }
 
     let mut files = Vec::<String>::new();
@@ -120,37 +244,7 @@ pub async fn ls(
                 // Someone provided just a plain path to dir
                 log::debug!("Path is a dir");
 
-                let glob_pattern = std::path::Path::new(absolute_root_path_str)
-                    .join("**")
-                    .join("*.md")
-                    .to_str()
-                    .unwrap()
-                    .to_owned();
-                log::debug!("Searching files by glob pattern: {:#?}", &glob_pattern);
-
-                match glob::glob(&glob_pattern) {
-                    Ok(search_results) => {
-                        for search_result in search_results {
-                            match search_result {
-                                Ok(markdown_file_path) => {
-                                    match dunce::canonicalize(markdown_file_path) {
-                                        Ok(markdown_file_abs_path) => {
-                                            files.push(markdown_file_abs_path.display().to_string())
-                                        }
-                                        Err(error) => warn!(
-                                        "Cannot obtain an absolute path to found file, error: {}",
-                                        &error
-                                    ),
-                                    }
-                                }
-                                Err(error) => {
-                                    warn!("Found a Markdown file, but it had an error: {}", &error)
-                                }
-                            }
-                        }
-                    }
-                    Err(error) => warn!("Unable to perform a glob search, error: {}", &error),
-                }
+                files.append(&mut find_markdown_files(&absolute_root_path, config));
             } else {
                 warn!("Unable to collect markdown files: path is neither a file nor a dir");
             }
@@ -176,18 +270,35 @@ pub async fn ls(
     }
 
     // Filter files by exclude patterns
-    markdown_files.retain(|markdown_file| {
-        let unix_style_file_path = Path::new(&markdown_file.path)
-            .to_slash()
-            .unwrap_or(Cow::from(markdown_file.path.clone()));
-        for exclude_pattern in exclude {
-            if WildMatch::new(exclude_pattern).matches(&unix_style_file_path) {
-                log::debug!("Ignoring {:#?}", &markdown_file.path);
-                return false;
-            }
-        }
-        true
-    });
+    markdown_files.retain(|markdown_file| !is_excluded(&markdown_file.path, &config.exclude));
 
     markdown_files
 }
+
+/// When path is a remote git repo or an archive, returns the dir where it is checked out(extracted)
+/// along with a prefix to report paths with instead of that dir, i.e. "docs.zip!/"
+pub fn display_root(path: &str) -> Option<(std::path::PathBuf, String)> {
+    if let Some(remote) = RemoteRepo::parse(path) {
+        let dir = dunce::canonicalize(remote.cache_dir()).ok()?;
+        return Some((dir, String::new()));
+    }
+    if archive::Format::from_path(path).is_some() && Path::new(path).is_file() {
+        let dir = dunce::canonicalize(archive::extract_dir(Path::new(path)).ok()?).ok()?;
+        return Some((dir, format!("{path}!/")));
+    }
+    None
+}
+
+/// Checks whether file path matches any of exclude patterns
+pub fn is_excluded(path: &str, exclude: &[String]) -> bool {
+    let unix_style_file_path = Path::new(path)
+        .to_slash()
+        .unwrap_or(Cow::from(path.to_owned()));
+    for exclude_pattern in exclude {
+        if WildMatch::new(exclude_pattern).matches(&unix_style_file_path) {
+            log::debug!("Ignoring {:#?}", &path);
+            return true;
+        }
+    }
+    false
+}
@@ -0,0 +1,146 @@
+use auth_git2::GitAuthenticator;
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+/// Local ref that points to the fetched commit in the cached clone
+const FETCHED_REF: &str = "refs/checkmark/fetched";
+
+/// Remote git repository to check. Parsed from `<url>.git[#<ref>][:<sub-path>]`,
+/// i.e. `https://github.com/vvvar/checkmark.git#main:docs`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteRepo {
+    pub url: String,
+    /// Branch or tag to check, remote HEAD when not set
+    pub reference: Option<String>,
+    /// Dir inside of the repo to check, whole repo when not set
+    pub subpath: Option<String>,
+}
+
+impl RemoteRepo {
+    /// Returns None when input is not a git repo URL
+    pub fn parse(input: &str) -> Option<Self> {
+        let (url, fragment) = match input.split_once('#') {
+            Some((url, fragment)) => (url, Some(fragment)),
+            None => (input, None),
+        };
+        if !url.ends_with(".git") {
+            return None;
+        }
+        let (reference, subpath) = match fragment {
+            Some(fragment) => match fragment.split_once(':') {
+                Some((reference, subpath)) => (reference, subpath),
+                None => (fragment, ""),
+            },
+            None => ("", ""),
+        };
+        let non_empty = |value: &str| match value.trim_matches('/') {
+            "" => None,
+            value => Some(value.to_string()),
+        };
+        Some(Self {
+            url: url.to_string(),
+            reference: non_empty(reference),
+            subpath: non_empty(subpath),
+        })
+    }
+
+    /// Dir where the clone is cached between runs, unique per URL.
+    /// Its name shall stay the same across releases, so the URL is hashed with a stable algorithm
+    pub fn cache_dir(&self) -> PathBuf {
+        let hash = format!("{:x}", Sha256::digest(self.url.as_bytes()));
+        let stem = std::path::Path::new(&self.url)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("repo");
+        std::env::temp_dir()
+            .join("checkmark")
+            .join("repos")
+            .join(format!("{}-{}", stem, &hash[..16]))
+    }
+
+    /// libgit2 supports shallow fetch only over the network
+    fn is_local(&self) -> bool {
+        self.url.starts_with("file://") || std::path::Path::new(&self.url).exists()
+    }
+
+    /// Fetch requested ref into the cached clone and check it out.
+    /// Only the latest commit is fetched and, when sub-path is set, only it is checked out.
+    /// Returns a dir to look for Markdown files in
+    pub fn checkout(&self) -> Result<PathBuf, git2::Error> {
+        let dir = self.cache_dir();
+        let repo = match git2::Repository::open(&dir) {
+            Ok(repo) => {
+                log::debug!("Reusing cached clone {:#?}", &dir);
+                repo
+            }
+            Err(_) => {
+                if dir.exists() {
+                    log::debug!("Directory {:#?} is not a git repo, removing", &dir);
+                    std::fs::remove_dir_all(&dir)
+                        .map_err(|err| git2::Error::from_str(&err.to_string()))?;
+                }
+                log::debug!("Cloning into the {:#?}", &dir);
+                git2::Repository::init(&dir)?
+            }
+        };
+
+        let auth = GitAuthenticator::default();
+        let git_config = git2::Config::open_default()?;
+        let mut cb = git2::RemoteCallbacks::new();
+        cb.transfer_progress(|stats| {
+            log::trace!(
+                "transfer_progress callback, stats.received_objects(): {}/{}",
+                &stats.received_objects(),
+                &stats.total_objects()
+            );
+            true
+        });
+        cb.credentials(auth.credentials(&git_config));
+        let mut fo = git2::FetchOptions::new();
+        fo.remote_callbacks(cb);
+        if !self.is_local() {
+            fo.depth(1);
+        }
+
+        let refspec = format!(
+            "+{}:{}",
+            self.reference.as_deref().unwrap_or("HEAD"),
+            FETCHED_REF
+        );
+        log::debug!("Fetching {:#?} from {:#?}", &refspec, &self.url);
+        repo.remote_anonymous(&self.url)?
+            .fetch(&[&refspec], Some(&mut fo), None)?;
+        let commit = repo.find_reference(FETCHED_REF)?.peel_to_commit()?;
+
+        let mut co = git2::build::CheckoutBuilder::new();
+        co.force().remove_untracked(true);
+        if let Some(subpath) = &self.subpath {
+            co.path(subpath);
+        }
+        co.progress(|path, cur, total| {
+            if let Some(path) = path {
+                log::trace!(
+                    "progress callback, path: {} {}/{}",
+                    &path.display(),
+                    cur,
+                    total
+                );
+            }
+        });
+        repo.checkout_tree(commit.as_object(), Some(&mut co))?;
+        repo.set_head_detached(commit.id())?;
+        log::debug!("Checked out {} into the {:#?}", commit.id(), &dir);
+
+        let root = match &self.subpath {
+            Some(subpath) => dir.join(subpath),
+            None => dir,
+        };
+        match root.exists() {
+            true => Ok(root),
+            false => Err(git2::Error::from_str(&format!(
+                "Path {:#?} does not exist in the repo",
+                self.subpath.as_deref().unwrap_or_default()
+            ))),
+        }
+    }
+}
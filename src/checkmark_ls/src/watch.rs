@@ -0,0 +1,142 @@
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+/// Editors usually write a file in several steps(truncate, write, rename),
+/// wait until things settle down before reporting a change
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// What has changed on disk since the last check
+#[derive(Debug, PartialEq, Eq)]
+pub enum Change {
+    /// Config file has been changed, everything shall be re-checked
+    Config,
+    /// Markdown files that were created or modified(absolute paths)
+    Files(Vec<String>),
+}
+
+/// Watches project root for changes of Markdown and config files
+pub struct Watcher {
+    _debouncer: Debouncer<RecommendedWatcher>,
+    events: UnboundedReceiver<Vec<PathBuf>>,
+    root: PathBuf,
+    config_path: Option<PathBuf>,
+    config: common::GlobalConfig,
+}
+
+impl Watcher {
+    /// Start watching the project root(either dir or a single file).
+    /// Config file could be located outside of the project root,
+    /// in that case its dir is watched too
+    pub fn new(
+        root: &str,
+        config_path: &Option<String>,
+        config: &common::GlobalConfig,
+    ) -> Result<Self, notify_debouncer_mini::notify::Error> {
+        let root = dunce::canonicalize(root)?;
+        let config_path = config_path
+            .as_ref()
+            .and_then(|path| dunce::canonicalize(path).ok());
+
+        let (sender, events) = unbounded_channel();
+        let mut debouncer =
+            new_debouncer(
+                DEBOUNCE_TIMEOUT,
+                move |result: DebounceEventResult| match result {
+                    Ok(events) => {
+                        let paths = events.into_iter().map(|event| event.path).collect();
+                        sender.send(paths).ok();
+                    }
+                    Err(error) => log::warn!("Unable to watch files: {}", error),
+                },
+            )?;
+
+        log::debug!("Watching {:#?}", &root);
+        let mode = match root.is_dir() {
+            true => RecursiveMode::Recursive,
+            false => RecursiveMode::NonRecursive,
+        };
+        debouncer.watcher().watch(&root, mode)?;
+        if let Some(config_dir) = config_path.as_ref().and_then(|path| path.parent()) {
+            if !config_dir.starts_with(&root) {
+                log::debug!("Watching config dir {:#?}", &config_dir);
+                debouncer
+                    .watcher()
+                    .watch(config_dir, RecursiveMode::NonRecursive)?;
+            }
+        }
+
+        Ok(Self {
+            _debouncer: debouncer,
+            events,
+            root,
+            config_path,
+            config: config.clone(),
+        })
+    }
+
+    /// Config location could change after re-reading config
+    pub fn set_config_path(&mut self, config_path: &Option<String>) {
+        self.config_path = config_path
+            .as_ref()
+            .and_then(|path| dunce::canonicalize(path).ok());
+    }
+
+    /// Same as `set_config_path`, but for the discovery options
+    pub fn set_config(&mut self, config: &common::GlobalConfig) {
+        self.config = config.clone();
+    }
+
+    /// Wait for the next relevant change.
+    /// Returns None when watching has stopped
+    pub async fn next(&mut self) -> Option<Change> {
+        loop {
+            let paths = self.events.recv().await?;
+            log::debug!("Changed paths: {:#?}", &paths);
+            if let Some(change) = self.classify(&paths) {
+                return Some(change);
+            }
+        }
+    }
+
+    /// Either the root config or any of nested ones
+    fn is_config(&self, path: &Path) -> bool {
+        self.config_path
+            .as_ref()
+            .map(|config_path| path.eq(config_path))
+            .unwrap_or(false)
+            || path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| common::CONFIG_FILE_NAMES.contains(&name))
+                .unwrap_or(false)
+    }
+
+    fn classify(&self, paths: &[PathBuf]) -> Option<Change> {
+        if paths.iter().any(|path| self.is_config(path)) {
+            return Some(Change::Config);
+        }
+        let matcher = crate::include_matcher(&self.config);
+        let is_dir = self.root.is_dir();
+        let mut files = paths
+            .iter()
+            .filter(|path| path.is_file())
+            .filter_map(|path| dunce::canonicalize(path).ok())
+            // Single watched file is checked regardless of include patterns
+            .filter(|path| !is_dir || crate::is_included(&matcher, &self.root, path))
+            // Changed files could be ignored by .gitignore and friends,
+            // same rules as for the discovery shall apply to them
+            .filter(|path| !is_dir || self.config.no_ignore || !crate::is_ignored(path))
+            .map(|path| path.display().to_string())
+            .filter(|path| !crate::is_excluded(path, &self.config.exclude))
+            .collect::<Vec<String>>();
+        files.sort();
+        files.dedup();
+        match files.is_empty() {
+            true => None,
+            false => Some(Change::Files(files)),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::io::Write;
+
+/// Files packed into every archive
+const FILES: [(&str, &str); 3] = [
+    ("README.md", "# Readme\n"),
+    ("docs/guide.md", "# Guide\n"),
+    ("docs/image.png", ""),
+];
+
+fn archive_path(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir().join("checkmark_archive_tests");
+    std::fs::create_dir_all(&dir).unwrap();
+    dir.join(name)
+}
+
+fn create_zip(name: &str, files: &[(&str, &str)]) -> std::path::PathBuf {
+    let path = archive_path(name);
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+    for (file, content) in files {
+        zip.start_file(*file, zip::write::FileOptions::default())
+            .unwrap();
+        zip.write_all(content.as_bytes()).unwrap();
+    }
+    zip.finish().unwrap();
+    path
+}
+
+fn create_tar_gz(name: &str, files: &[(&str, &str)]) -> std::path::PathBuf {
+    let path = archive_path(name);
+    let encoder = flate2::write::GzEncoder::new(
+        std::fs::File::create(&path).unwrap(),
+        flate2::Compression::default(),
+    );
+    let mut tar = tar::Builder::new(encoder);
+    for (file, content) in files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_cksum();
+        tar.append_data(&mut header, file, content.as_bytes())
+            .unwrap();
+    }
+    tar.into_inner().unwrap().finish().unwrap();
+    path
+}
+
+/// Lists files of the archive the way they are reported
+async fn ls(archive: &std::path::Path) -> Vec<String> {
+    let archive = archive.to_str().unwrap();
+    let tui = common::tui::CheckProgressTUI::new_thread_safe(true);
+    let files = checkmark_ls::ls(archive, &common::GlobalConfig::default(), &tui).await;
+    if let Some((root, prefix)) = checkmark_ls::display_root(archive) {
+        tui.lock().unwrap().set_root(&root, &prefix);
+    }
+    files
+        .iter()
+        .map(|file| tui.lock().unwrap().display_path(&file.path))
+        .collect()
+}
+
+#[tokio::test]
+async fn zip() {
+    let archive = create_zip("docs.zip", &FILES);
+    assert_eq!(
+        ls(&archive).await,
+        vec![
+            format!("{}!/README.md", archive.display()),
+            format!("{}!/docs/guide.md", archive.display())
+        ]
+    );
+}
+
+#[tokio::test]
+async fn tar_gz() {
+    let archive = create_tar_gz("docs.tar.gz", &FILES);
+    assert_eq!(
+        ls(&archive).await,
+        vec![
+            format!("{}!/README.md", archive.display()),
+            format!("{}!/docs/guide.md", archive.display())
+        ]
+    );
+}
+
+#[tokio::test]
+async fn entries_outside_of_archive_are_rejected() {
+    let archive = create_zip("evil.zip", &[("../evil.md", "# Evil\n")]);
+    assert!(ls(&archive).await.is_empty());
+    assert!(!archive.parent().unwrap().join("evil.md").exists());
+}
@@ -0,0 +1,162 @@
+use checkmark_ls::{changed_files, changed_lines, last_commit_times, GitChanges};
+use std::path::{Path, PathBuf};
+
+/// Creates a git repo with a single commit:
+///   - committed.md
+///   - modified.md
+///   - deleted.md
+///
+/// And then changes working tree:
+///   - modified.md is modified but not staged
+///   - deleted.md is deleted
+///   - staged.md is added to the index
+///   - untracked.md is created
+fn create_repo(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join("checkmark_changes_tests")
+        .join(name);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    std::fs::create_dir_all(&dir).unwrap();
+    let dir = dunce::canonicalize(dir).unwrap();
+
+    let repo = git2::Repository::init(&dir).unwrap();
+    for file in ["committed.md", "modified.md", "deleted.md"] {
+        std::fs::write(dir.join(file), "# Heading\n").unwrap();
+    }
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = git2::Signature::now("checkmark", "checkmark@example.com").unwrap();
+    repo.commit(Some("HEAD"), &signature, &signature, "Initial", &tree, &[])
+        .unwrap();
+
+    std::fs::write(dir.join("modified.md"), "# Modified\n").unwrap();
+    std::fs::remove_file(dir.join("deleted.md")).unwrap();
+    std::fs::write(dir.join("staged.md"), "# Staged\n").unwrap();
+    index.add_path(Path::new("staged.md")).unwrap();
+    index.write().unwrap();
+    std::fs::write(dir.join("untracked.md"), "# Untracked\n").unwrap();
+    dir
+}
+
+fn paths(dir: &Path, files: &[&str]) -> Vec<String> {
+    files
+        .iter()
+        .map(|file| dir.join(file).display().to_string())
+        .collect()
+}
+
+#[test]
+fn files_changed_since_ref() {
+    let dir = create_repo("since_ref");
+    assert_eq!(
+        changed_files(
+            dir.to_str().unwrap(),
+            &GitChanges::Since("HEAD".to_string())
+        )
+        .unwrap(),
+        paths(&dir, &["modified.md", "staged.md", "untracked.md"])
+    );
+}
+
+#[test]
+fn staged_files() {
+    let dir = create_repo("staged");
+    assert_eq!(
+        changed_files(dir.to_str().unwrap(), &GitChanges::Staged).unwrap(),
+        paths(&dir, &["staged.md"])
+    );
+}
+
+#[test]
+fn unknown_ref() {
+    let dir = create_repo("unknown_ref");
+    assert!(changed_files(
+        dir.to_str().unwrap(),
+        &GitChanges::Since("this-ref-does-not-exist".to_string())
+    )
+    .is_err());
+}
+
+#[test]
+fn lines_changed_since_ref() {
+    let dir = create_repo("lines_since_ref");
+    std::fs::write(
+        dir.join("modified.md"),
+        "# Heading\n\nAdded\nlines\n\nUnchanged\n\nModified\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("committed.md"), "# Heading\n\nAdded\nlines\n").unwrap();
+    let repo = git2::Repository::open(&dir).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("committed.md")).unwrap();
+    index.write().unwrap();
+    std::fs::write(
+        dir.join("committed.md"),
+        "# Heading\n\nAdded\nlines\n\nUnchanged\n",
+    )
+    .unwrap();
+
+    let lines = changed_lines(
+        dir.to_str().unwrap(),
+        &GitChanges::Since("HEAD".to_string()),
+    )
+    .unwrap();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[&paths(&dir, &["modified.md"])[0]], vec![2..=8]);
+    assert_eq!(lines[&paths(&dir, &["committed.md"])[0]], vec![2..=6]);
+    assert_eq!(lines[&paths(&dir, &["staged.md"])[0]], vec![1..=1]);
+    assert_eq!(lines[&paths(&dir, &["untracked.md"])[0]], vec![1..=1]);
+
+    let lines = changed_lines(dir.to_str().unwrap(), &GitChanges::Staged).unwrap();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[&paths(&dir, &["committed.md"])[0]], vec![2..=4]);
+}
+
+#[test]
+fn last_commits_of_files() {
+    let dir = create_repo("last_commits");
+    // Commit modified and staged files back in time
+    let repo = git2::Repository::open(&dir).unwrap();
+    let mut index = repo.index().unwrap();
+    index.add_path(Path::new("modified.md")).unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let signature = git2::Signature::new(
+        "checkmark",
+        "checkmark@example.com",
+        &git2::Time::new(1_000_000_000, 0),
+    )
+    .unwrap();
+    let parent = repo.head().unwrap().peel_to_commit().unwrap();
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        "Back in time",
+        &tree,
+        &[&parent],
+    )
+    .unwrap();
+
+    let times = last_commit_times(
+        dir.to_str().unwrap(),
+        &paths(
+            &dir,
+            &["committed.md", "modified.md", "staged.md", "untracked.md"],
+        ),
+    )
+    .unwrap();
+    assert_eq!(times.len(), 3);
+    assert_eq!(times[&paths(&dir, &["modified.md"])[0]], 1_000_000_000);
+    assert_eq!(times[&paths(&dir, &["staged.md"])[0]], 1_000_000_000);
+    assert_eq!(
+        times[&paths(&dir, &["committed.md"])[0]],
+        parent.time().seconds()
+    );
+}
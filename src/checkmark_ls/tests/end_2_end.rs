@@ -10,6 +10,11 @@ fn activate_logging() {
 #[tokio::test]
 async fn ls() {
     let tui = common::tui::CheckProgressTUI::new_thread_safe(true);
-    let files = checkmark_ls::ls("https://github.com/google/googletest.git", &vec![], &tui).await;
+    let files = checkmark_ls::ls(
+        "https://github.com/google/googletest.git",
+        &common::GlobalConfig::default(),
+        &tui,
+    )
+    .await;
     assert_eq!(files.len(), 27); // There are some files in the repo
 }
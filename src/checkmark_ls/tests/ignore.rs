@@ -0,0 +1,89 @@
+/// Creates a project with ignore files:
+///   - README.md
+///   - .github/PULL_REQUEST_TEMPLATE.md
+///   - node_modules/package/README.md(ignored by .gitignore)
+///   - build/output.md(ignored by .ignore)
+///   - drafts/draft.md(ignored by .checkmarkignore)
+fn create_project(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir()
+        .join("checkmark_ignore_tests")
+        .join(name);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    let files = [
+        ("README.md", "# Readme\n"),
+        (".github/PULL_REQUEST_TEMPLATE.md", "# Template\n"),
+        ("node_modules/package/README.md", "# Package\n"),
+        ("build/output.md", "# Output\n"),
+        ("drafts/draft.md", "# Draft\n"),
+        (".gitignore", "node_modules/\n"),
+        (".ignore", "build/\n"),
+        (".checkmarkignore", "drafts/\n"),
+    ];
+    for (path, content) in files {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+    dunce::canonicalize(dir).unwrap()
+}
+
+async fn ls(dir: &std::path::Path, config: &common::GlobalConfig) -> Vec<String> {
+    let tui = common::tui::CheckProgressTUI::new_thread_safe(true);
+    checkmark_ls::ls(dir.to_str().unwrap(), config, &tui)
+        .await
+        .into_iter()
+        .map(|file| file.path)
+        .collect()
+}
+
+fn paths(dir: &std::path::Path, files: &[&str]) -> Vec<String> {
+    files
+        .iter()
+        .map(|file| dir.join(file).display().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn ignored_files_are_skipped() {
+    let dir = create_project("skipped");
+    assert_eq!(
+        ls(&dir, &common::GlobalConfig::default()).await,
+        paths(&dir, &[".github/PULL_REQUEST_TEMPLATE.md", "README.md"])
+    );
+}
+
+#[tokio::test]
+async fn ignore_files_could_be_disabled() {
+    let dir = create_project("disabled");
+    assert_eq!(
+        ls(
+            &dir,
+            &common::GlobalConfig {
+                no_ignore: true,
+                ..Default::default()
+            }
+        )
+        .await,
+        paths(
+            &dir,
+            &[
+                ".github/PULL_REQUEST_TEMPLATE.md",
+                "README.md",
+                "build/output.md",
+                "drafts/draft.md",
+                "node_modules/package/README.md",
+            ]
+        )
+    );
+}
+
+#[tokio::test]
+async fn explicitly_provided_file_is_not_ignored() {
+    let dir = create_project("explicit");
+    let file = dir.join("drafts/draft.md");
+    let tui = common::tui::CheckProgressTUI::new_thread_safe(true);
+    let files = checkmark_ls::ls(file.to_str().unwrap(), &Default::default(), &tui).await;
+    assert_eq!(files.len(), 1);
+}
@@ -0,0 +1,72 @@
+/// Creates a project with Markdown files of different extensions:
+///   - README.md
+///   - docs/guide.markdown
+///   - docs/notes.mdown
+///   - docs/component.mdx
+///   - docs/page.txt
+fn create_project(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir()
+        .join("checkmark_include_tests")
+        .join(name);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    for path in [
+        "README.md",
+        "docs/guide.markdown",
+        "docs/notes.mdown",
+        "docs/component.mdx",
+        "docs/page.txt",
+    ] {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, "# Heading\n").unwrap();
+    }
+    dunce::canonicalize(dir).unwrap()
+}
+
+async fn ls(dir: &std::path::Path, config: &common::GlobalConfig) -> Vec<String> {
+    let tui = common::tui::CheckProgressTUI::new_thread_safe(true);
+    checkmark_ls::ls(dir.to_str().unwrap(), config, &tui)
+        .await
+        .into_iter()
+        .map(|file| file.path)
+        .collect()
+}
+
+fn paths(dir: &std::path::Path, files: &[&str]) -> Vec<String> {
+    files
+        .iter()
+        .map(|file| dir.join(file).display().to_string())
+        .collect()
+}
+
+#[tokio::test]
+async fn common_markdown_extensions_are_discovered() {
+    let dir = create_project("default");
+    assert_eq!(
+        ls(&dir, &common::GlobalConfig::default()).await,
+        paths(
+            &dir,
+            &[
+                "README.md",
+                "docs/component.mdx",
+                "docs/guide.markdown",
+                "docs/notes.mdown"
+            ]
+        )
+    );
+}
+
+#[tokio::test]
+async fn custom_include_patterns() {
+    let dir = create_project("custom");
+    let config = common::GlobalConfig {
+        include: vec!["docs/*.{txt,mdx}".to_string()],
+        ..Default::default()
+    };
+    assert_eq!(
+        ls(&dir, &config).await,
+        paths(&dir, &["docs/component.mdx", "docs/page.txt"])
+    );
+}
@@ -0,0 +1,135 @@
+use checkmark_ls::RemoteRepo;
+
+/// Creates a bare repo with two commits:
+///   1. README.md, docs/guide.md - tagged as v1
+///   2. README.md, docs/guide.md, docs/new.md - on the main branch
+fn create_bare_repo(name: &str) -> std::path::PathBuf {
+    let dir = std::env::temp_dir()
+        .join("checkmark_remote_tests")
+        .join(format!("{name}.git"));
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+    let repo = git2::Repository::init_bare(&dir).unwrap();
+    let signature = git2::Signature::now("checkmark", "checkmark@example.com").unwrap();
+    let tree = |docs: &[&str]| {
+        let mut docs_tree = repo.treebuilder(None).unwrap();
+        for doc in docs {
+            let blob = repo.blob(b"# Guide\n").unwrap();
+            docs_tree.insert(doc, blob, 0o100644).unwrap();
+        }
+        let mut root_tree = repo.treebuilder(None).unwrap();
+        root_tree
+            .insert("README.md", repo.blob(b"# Readme\n").unwrap(), 0o100644)
+            .unwrap();
+        root_tree
+            .insert("docs", docs_tree.write().unwrap(), 0o040000)
+            .unwrap();
+        repo.find_tree(root_tree.write().unwrap()).unwrap()
+    };
+    let first = repo
+        .commit(
+            None,
+            &signature,
+            &signature,
+            "First",
+            &tree(&["guide.md"]),
+            &[],
+        )
+        .unwrap();
+    let first = repo.find_commit(first).unwrap();
+    repo.tag_lightweight("v1", first.as_object(), false)
+        .unwrap();
+    repo.commit(
+        Some("refs/heads/main"),
+        &signature,
+        &signature,
+        "Second",
+        &tree(&["guide.md", "new.md"]),
+        &[&first],
+    )
+    .unwrap();
+    repo.set_head("refs/heads/main").unwrap();
+    dunce::canonicalize(dir).unwrap()
+}
+
+fn url(dir: &std::path::Path, fragment: &str) -> String {
+    format!("file://{}{}", dir.display(), fragment)
+}
+
+/// Lists files of the remote repo, paths are relative to the repo
+async fn ls(url: &str) -> Vec<String> {
+    let tui = common::tui::CheckProgressTUI::new_thread_safe(true);
+    let files = checkmark_ls::ls(url, &common::GlobalConfig::default(), &tui).await;
+    let (root, prefix) = checkmark_ls::display_root(url).unwrap();
+    tui.lock().unwrap().set_root(&root, &prefix);
+    files
+        .iter()
+        .map(|file| tui.lock().unwrap().display_path(&file.path))
+        .collect()
+}
+
+#[test]
+fn parse_url() {
+    assert_eq!(RemoteRepo::parse("README.md"), None);
+    assert_eq!(
+        RemoteRepo::parse("git@github.com:vvvar/checkmark.git"),
+        Some(RemoteRepo {
+            url: "git@github.com:vvvar/checkmark.git".to_string(),
+            reference: None,
+            subpath: None,
+        })
+    );
+    assert_eq!(
+        RemoteRepo::parse("https://github.com/vvvar/checkmark.git#v1.0:docs/"),
+        Some(RemoteRepo {
+            url: "https://github.com/vvvar/checkmark.git".to_string(),
+            reference: Some("v1.0".to_string()),
+            subpath: Some("docs".to_string()),
+        })
+    );
+    assert_eq!(
+        RemoteRepo::parse("https://github.com/vvvar/checkmark.git#:docs"),
+        Some(RemoteRepo {
+            url: "https://github.com/vvvar/checkmark.git".to_string(),
+            reference: None,
+            subpath: Some("docs".to_string()),
+        })
+    );
+}
+
+#[tokio::test]
+async fn default_branch() {
+    let dir = create_bare_repo("default_branch");
+    assert_eq!(
+        ls(&url(&dir, "")).await,
+        vec!["README.md", "docs/guide.md", "docs/new.md"]
+    );
+}
+
+#[tokio::test]
+async fn tag_and_subpath() {
+    let dir = create_bare_repo("tag_and_subpath");
+    assert_eq!(ls(&url(&dir, "#v1:docs")).await, vec!["docs/guide.md"]);
+}
+
+#[tokio::test]
+async fn cached_clone_is_reused() {
+    let dir = create_bare_repo("cached_clone");
+    let remote = RemoteRepo::parse(&url(&dir, "")).unwrap();
+    remote.checkout().unwrap();
+    let marker = remote.cache_dir().join(".git").join("checkmark-marker");
+    std::fs::write(&marker, "").unwrap();
+    assert_eq!(
+        ls(&url(&dir, "#v1")).await,
+        vec!["README.md", "docs/guide.md"]
+    );
+    assert!(marker.exists());
+}
+
+#[tokio::test]
+async fn unknown_subpath() {
+    let dir = create_bare_repo("unknown_subpath");
+    let remote = RemoteRepo::parse(&url(&dir, "#main:missing")).unwrap();
+    assert!(remote.checkout().is_err());
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+/// Creates an empty dir for the test, removes previous one if any
+fn test_dir(name: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir()
+        .join("checkmark_watch_tests")
+        .join(name);
+    if path.exists() {
+        std::fs::remove_dir_all(&path).unwrap();
+    }
+    std::fs::create_dir_all(&path).unwrap();
+    dunce::canonicalize(path).unwrap()
+}
+
+async fn next_change(watcher: &mut checkmark_ls::Watcher) -> checkmark_ls::Change {
+    tokio::time::timeout(Duration::from_secs(10), watcher.next())
+        .await
+        .expect("No change detected")
+        .unwrap()
+}
+
+#[tokio::test]
+async fn watch_markdown_files() {
+    let dir = test_dir("markdown");
+    std::fs::create_dir_all(dir.join("excluded")).unwrap();
+    std::fs::write(dir.join(".gitignore"), "ignored/\n").unwrap();
+    std::fs::create_dir_all(dir.join("ignored")).unwrap();
+    let mut watcher = checkmark_ls::Watcher::new(
+        dir.to_str().unwrap(),
+        &None,
+        &common::GlobalConfig {
+            exclude: vec!["**/excluded/**".to_string()],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+
+    std::fs::write(dir.join("notes.txt"), "Not a Markdown").unwrap();
+    std::fs::write(dir.join("excluded").join("skip.md"), "# Skip\n").unwrap();
+    std::fs::write(dir.join("ignored").join("skip.md"), "# Skip\n").unwrap();
+    std::fs::write(dir.join("README.md"), "# Readme\n").unwrap();
+
+    assert_eq!(
+        next_change(&mut watcher).await,
+        checkmark_ls::Change::Files(vec![dir.join("README.md").display().to_string()])
+    );
+}
+
+#[tokio::test]
+async fn watch_config_file() {
+    let dir = test_dir("config");
+    let mut watcher =
+        checkmark_ls::Watcher::new(dir.to_str().unwrap(), &None, &Default::default()).unwrap();
+
+    std::fs::write(dir.join("checkmark.toml"), "[style]\n").unwrap();
+
+    assert_eq!(
+        next_change(&mut watcher).await,
+        checkmark_ls::Change::Config
+    );
+}
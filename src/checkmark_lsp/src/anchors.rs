@@ -0,0 +1,208 @@
+use crate::position::offsets_to_range;
+use markdown::mdast::{Heading, Link, Node};
+use tower_lsp::lsp_types::{DocumentLink, Hover, HoverContents, MarkupContent, MarkupKind, Url};
+
+fn extract_links(ast: &Node) -> Vec<&Link> {
+    common::ast::BfsIterator::from(ast)
+        .filter_map(|n| common::ast::try_cast_to_link(n))
+        .collect::<Vec<&Link>>()
+}
+
+fn extract_headings(ast: &Node) -> Vec<&Heading> {
+    common::ast::BfsIterator::from(ast)
+        .filter_map(|n| common::ast::try_cast_to_heading(n))
+        .collect::<Vec<&Heading>>()
+}
+
+/// Find a heading that could be reached with the provided fragment, e.g. "#about-us"
+fn find_heading<'a>(headings: &[&'a Heading], fragment: &str) -> Option<&'a Heading> {
+    headings
+        .iter()
+        .find(|heading| common::ast::heading_to_fragment(heading).eq(fragment))
+        .copied()
+}
+
+/// Text of the heading without any Markdown markup
+fn heading_text(heading: &Heading) -> String {
+    heading
+        .children
+        .iter()
+        .map(|node| node.to_string())
+        .collect::<String>()
+}
+
+fn heading_line(heading: &Heading) -> Option<usize> {
+    heading
+        .position
+        .as_ref()
+        .map(|position| position.start.line)
+}
+
+/// Links with the scheme(https://, mailto:) are not files,
+/// editors are already able to open them
+fn is_local_file(url: &str) -> bool {
+    Url::parse(url).is_err()
+}
+
+/// Resolve link to the target editor could open.
+/// Fragments are resolved to the line of the heading they point to
+fn resolve(document: &Url, headings: &[&Heading], url: &str) -> Option<Url> {
+    if let Some(fragment) = url.strip_prefix('#') {
+        let heading = find_heading(headings, &format!("#{fragment}"))?;
+        let mut target = document.clone();
+        target.set_fragment(Some(&format!("L{}", heading_line(heading)?)));
+        return Some(target);
+    }
+    if !is_local_file(url) {
+        return Url::parse(url).ok();
+    }
+    let (path, fragment) = match url.split_once('#') {
+        Some((path, fragment)) => (path, Some(fragment)),
+        None => (url, None),
+    };
+    let path = document.to_file_path().ok()?.parent()?.join(path);
+    if !path.exists() {
+        return None;
+    }
+    let mut target = Url::from_file_path(&path).ok()?;
+    if let Some(fragment) = fragment {
+        let content = std::fs::read_to_string(&path).ok()?;
+        let ast = common::ast::parse(&content).ok()?;
+        let headings = extract_headings(&ast);
+        if let Some(line) = find_heading(&headings, &format!("#{fragment}")).and_then(heading_line)
+        {
+            target.set_fragment(Some(&format!("L{line}")));
+        }
+    }
+    Some(target)
+}
+
+/// Get all links of the document that could be followed from the editor.
+/// Links to heading anchors(e.g. "#about-us") and local files are resolved,
+/// links that point to nowhere are skipped
+pub fn document_links(document: &Url, text: &str) -> Vec<DocumentLink> {
+    let ast = match common::ast::parse(text) {
+        Ok(ast) => ast,
+        Err(_) => return vec![],
+    };
+    let headings = extract_headings(&ast);
+    extract_links(&ast)
+        .iter()
+        .filter_map(|link| {
+            let position = link.position.as_ref()?;
+            Some(DocumentLink {
+                range: offsets_to_range(text, position.start.offset, position.end.offset),
+                target: Some(resolve(document, &headings, &link.url)?),
+                tooltip: None,
+                data: None,
+            })
+        })
+        .collect::<Vec<DocumentLink>>()
+}
+
+fn contains(node_position: &Option<markdown::unist::Position>, offset: usize) -> bool {
+    match node_position {
+        Some(position) => position.start.offset <= offset && offset <= position.end.offset,
+        None => false,
+    }
+}
+
+fn markdown_hover(value: String) -> Hover {
+    Hover {
+        contents: HoverContents::Markup(MarkupContent {
+            kind: MarkupKind::Markdown,
+            value,
+        }),
+        range: None,
+    }
+}
+
+/// Describe heading anchors under the cursor:
+///   - for a link to the fragment, show the heading it points to
+///   - for a heading, show the fragment that could be used to link it
+pub fn hover(text: &str, offset: usize) -> Option<Hover> {
+    let ast = common::ast::parse(text).ok()?;
+    let headings = extract_headings(&ast);
+    if let Some(link) = extract_links(&ast)
+        .iter()
+        .find(|link| link.url.starts_with('#') && contains(&link.position, offset))
+    {
+        return Some(markdown_hover(match find_heading(&headings, &link.url) {
+            Some(heading) => format!(
+                "Points to heading **{}** on line {}",
+                heading_text(heading),
+                heading_line(heading).unwrap_or_default()
+            ),
+            None => format!("No heading matches fragment `{}`", &link.url),
+        }));
+    }
+    headings
+        .iter()
+        .find(|heading| contains(&heading.position, offset))
+        .map(|heading| {
+            markdown_hover(format!(
+                "Anchor: `{}`",
+                common::ast::heading_to_fragment(heading)
+            ))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tower_lsp::lsp_types::{Position, Range};
+
+    fn hover_text(hover: Option<Hover>) -> Option<String> {
+        match hover?.contents {
+            HoverContents::Markup(content) => Some(content.value),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn fragment_link_resolved_to_heading_line() {
+        let document = Url::parse("file:///docs/README.md").unwrap();
+        let text = "# Title\n\nSee [about](#about-us).\n\n## About us\n";
+        let links = document_links(&document, text);
+        assert_eq!(links.len(), 1);
+        assert_eq!(
+            links[0].range,
+            Range::new(Position::new(2, 4), Position::new(2, 22))
+        );
+        assert_eq!(
+            links[0].target.as_ref().unwrap().as_str(),
+            "file:///docs/README.md#L5"
+        );
+    }
+
+    #[test]
+    fn broken_links_are_skipped() {
+        let document = Url::parse("file:///docs/README.md").unwrap();
+        let text = "[missing](#missing) and [file](this/file/does/not/exist.md)\n";
+        assert_eq!(document_links(&document, text), vec![]);
+    }
+
+    #[test]
+    fn hover_on_fragment_link() {
+        let text = "# Title\n\nSee [about](#about-us) and [other](#other).\n\n## About us\n";
+        assert_eq!(
+            hover_text(hover(text, 15)),
+            Some("Points to heading **About us** on line 5".to_string())
+        );
+        assert_eq!(
+            hover_text(hover(text, 40)),
+            Some("No heading matches fragment `#other`".to_string())
+        );
+    }
+
+    #[test]
+    fn hover_on_heading() {
+        let text = "# Title\n\n## About us\n";
+        assert_eq!(
+            hover_text(hover(text, 12)),
+            Some("Anchor: `#about-us`".to_string())
+        );
+        assert_eq!(hover_text(hover(text, 8)), None);
+    }
+}
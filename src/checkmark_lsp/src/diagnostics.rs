@@ -0,0 +1,144 @@
+use crate::position::offsets_to_range;
+use common::{CheckIssue, IssueCategory, IssueSeverity};
+use tower_lsp::lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString};
+
+/// Name of the diagnostics source shown by editors
+pub const SOURCE: &str = "checkmark";
+
+/// Lint messages are prefixed with the rule code, e.g. "MD001 - Message".
+/// Split them into code and message, other issues use their category as a code
+fn code_and_message(issue: &CheckIssue) -> (String, String) {
+    if let IssueCategory::Linting = issue.category {
+        if let Some((code, message)) = issue.message.split_once(" - ") {
+            return (code.to_string(), message.to_string());
+        }
+    }
+    let code = match &issue.category {
+        IssueCategory::Formatting => "Formatting",
+        IssueCategory::Linting => "Linting",
+        IssueCategory::LinkChecking => "LinkCheck",
+        IssueCategory::Spelling => "Spelling",
+        IssueCategory::Grammar => "Grammar",
+        IssueCategory::Review => "Review",
+        IssueCategory::FrontMatter => "FrontMatter",
+        IssueCategory::Stale => "Stale",
+    };
+    (code.to_string(), issue.message.clone())
+}
+
+/// Convert issue found in the text into the LSP diagnostic.
+/// Fixes are appended to the message, replacements are kept
+/// in the diagnostic data so code actions could be built from them
+pub fn to_diagnostic(issue: &CheckIssue, text: &str) -> Diagnostic {
+    let (code, message) = code_and_message(issue);
+    let severity = match &issue.severity {
+        IssueSeverity::Bug | IssueSeverity::Error => DiagnosticSeverity::ERROR,
+        IssueSeverity::Warning => DiagnosticSeverity::WARNING,
+        IssueSeverity::Note => DiagnosticSeverity::INFORMATION,
+        IssueSeverity::Help => DiagnosticSeverity::HINT,
+    };
+    let message = if issue.fixes.is_empty() {
+        message
+    } else {
+        format!("{}\n\n{}", message, issue.fixes.join("\n"))
+    };
+    Diagnostic {
+        range: offsets_to_range(text, issue.offset_start, issue.offset_end),
+        severity: Some(severity),
+        code: Some(NumberOrString::String(code)),
+        source: Some(SOURCE.to_string()),
+        message,
+        data: match issue.replacements.is_empty() {
+            true => None,
+            false => Some(serde_json::json!({ "replacements": issue.replacements })),
+        },
+        ..Diagnostic::default()
+    }
+}
+
+/// Take replacements that were stored in the diagnostic data
+pub fn replacements(diagnostic: &Diagnostic) -> Vec<String> {
+    diagnostic
+        .data
+        .as_ref()
+        .and_then(|data| data.get("replacements"))
+        .and_then(|replacements| replacements.as_array())
+        .map(|replacements| {
+            replacements
+                .iter()
+                .filter_map(|replacement| replacement.as_str())
+                .map(|replacement| replacement.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tower_lsp::lsp_types::{Position, Range};
+
+    #[test]
+    fn lint_issue_to_diagnostic() {
+        let text = "# Heading\n### Heading\n";
+        let issue = common::CheckIssueBuilder::default()
+            .set_category(IssueCategory::Linting)
+            .set_severity(IssueSeverity::Error)
+            .set_file_path("file.md".to_string())
+            .set_row_num_start(2)
+            .set_row_num_end(2)
+            .set_col_num_start(1)
+            .set_col_num_end(12)
+            .set_offset_start(10)
+            .set_offset_end(21)
+            .set_message(
+                "MD001 - Heading levels should only increment by one level at a time".to_string(),
+            )
+            .build();
+        let diagnostic = to_diagnostic(&issue, text);
+        assert_eq!(
+            diagnostic.range,
+            Range::new(Position::new(1, 0), Position::new(1, 11))
+        );
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(
+            diagnostic.code,
+            Some(NumberOrString::String("MD001".to_string()))
+        );
+        assert_eq!(
+            diagnostic.message,
+            "Heading levels should only increment by one level at a time"
+        );
+        assert_eq!(replacements(&diagnostic), Vec::<String>::new());
+    }
+
+    #[test]
+    fn spelling_issue_to_diagnostic() {
+        let text = "Some headr";
+        let issue = common::CheckIssueBuilder::default()
+            .set_category(IssueCategory::Spelling)
+            .set_severity(IssueSeverity::Warning)
+            .set_file_path("file.md".to_string())
+            .set_row_num_start(1)
+            .set_row_num_end(1)
+            .set_col_num_start(6)
+            .set_col_num_end(11)
+            .set_offset_start(5)
+            .set_offset_end(10)
+            .set_message("\"headr\": Unknown word".to_string())
+            .push_fix("Consider changing \"headr\" to \"head\"")
+            .push_replacement("head")
+            .build();
+        let diagnostic = to_diagnostic(&issue, text);
+        assert_eq!(
+            diagnostic.code,
+            Some(NumberOrString::String("Spelling".to_string()))
+        );
+        assert_eq!(
+            diagnostic.message,
+            "\"headr\": Unknown word\n\nConsider changing \"headr\" to \"head\""
+        );
+        assert_eq!(replacements(&diagnostic), vec!["head".to_string()]);
+    }
+}
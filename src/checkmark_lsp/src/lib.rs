@@ -0,0 +1,410 @@
+mod anchors;
+mod diagnostics;
+mod position;
+
+use checkmark_spelling::SpellChecker;
+use common::{Config, MarkDownFile};
+use position::{full_range, position_to_offset};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::{Notify, RwLock};
+use tower::Service;
+use tower_lsp::jsonrpc::{Request, Result};
+use tower_lsp::lsp_types::*;
+use tower_lsp::{Client, LanguageServer, LspService, Server};
+
+/// Resolves configs of the documents, whenever they are checked or formatted
+pub trait Configs: Send + Sync {
+    /// Effective config of the document: nested configs, overrides and its front matter applied
+    fn get(&self, file: &MarkDownFile) -> Config;
+
+    /// Config files have changed, configs shall not be resolved from what has been read before
+    fn invalidate(&self) {}
+}
+
+impl<F> Configs for F
+where
+    F: Fn(&MarkDownFile) -> Config + Send + Sync,
+{
+    fn get(&self, file: &MarkDownFile) -> Config {
+        self(file)
+    }
+}
+
+/// Whether the path(or URI) points to a config file
+fn is_config_file(uri: &Url) -> bool {
+    uri.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .is_some_and(|name| common::CONFIG_FILE_NAMES.contains(&name))
+}
+
+struct Backend {
+    client: Client,
+    configs: Arc<dyn Configs>,
+    /// Client is able to watch config files for the server
+    watch_config_files: AtomicBool,
+    /// Latest content of every opened document
+    documents: RwLock<HashMap<Url, String>>,
+    /// Loading dictionaries takes a while, so spell checker is created on the first use
+    /// and then shared by documents with the same words whitelist
+    spell_checkers: Arc<Mutex<HashMap<Vec<String>, Arc<SpellChecker>>>>,
+}
+
+impl Backend {
+    fn new(client: Client, configs: Arc<dyn Configs>) -> Self {
+        Self {
+            client,
+            configs,
+            watch_config_files: AtomicBool::new(false),
+            documents: RwLock::new(HashMap::new()),
+            spell_checkers: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    async fn document(&self, uri: &Url) -> Option<String> {
+        self.documents.read().await.get(uri).cloned()
+    }
+
+    fn to_markdown_file(uri: &Url, text: &str) -> MarkDownFile {
+        MarkDownFile {
+            path: match uri.to_file_path() {
+                Ok(path) => path.to_string_lossy().to_string(),
+                Err(_) => uri.to_string(),
+            },
+            content: text.to_string(),
+            issues: vec![],
+        }
+    }
+
+    /// Lint and spell check the document.
+    /// Checks are CPU-heavy, so they are executed outside of async runtime
+    async fn diagnose(&self, uri: &Url, text: &str) -> Vec<Diagnostic> {
+        let file = Self::to_markdown_file(uri, text);
+        let configs = self.configs.clone();
+        let spell_checkers = self.spell_checkers.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            let config = configs.get(&file);
+            let mut issues = vec![];
+            if !config.linter.skip {
+                issues.append(&mut checkmark_lint::lint(&file, &config));
+            }
+            if !config.spelling.skip {
+                let spell_checker = spell_checkers
+                    .lock()
+                    .unwrap()
+                    .entry(config.spelling.words_whitelist.clone())
+                    .or_insert_with(|| {
+                        Arc::new(checkmark_spelling::create_spell_checker(
+                            &config.spelling.words_whitelist,
+                        ))
+                    })
+                    .clone();
+                issues.append(&mut checkmark_spelling::spell_check(
+                    &spell_checker,
+                    &file,
+                    &config,
+                ));
+            }
+            issues
+                .iter()
+                .map(|issue| diagnostics::to_diagnostic(issue, &file.content))
+                .collect::<Vec<Diagnostic>>()
+        })
+        .await;
+        match result {
+            Ok(diagnostics) => diagnostics,
+            Err(err) => {
+                log::error!("Unable to check {}: {}", uri, err);
+                vec![]
+            }
+        }
+    }
+
+    async fn publish_diagnostics(&self, uri: Url, version: Option<i32>) {
+        if let Some(text) = self.document(&uri).await {
+            let diagnostics = self.diagnose(&uri, &text).await;
+            self.client
+                .publish_diagnostics(uri, diagnostics, version)
+                .await;
+        }
+    }
+
+    /// Config files have changed, so every opened document could have different issues now
+    async fn reload_configs(&self) {
+        log::debug!("Config files have changed, re-checking opened documents");
+        self.configs.invalidate();
+        let uris = self
+            .documents
+            .read()
+            .await
+            .keys()
+            .cloned()
+            .collect::<Vec<Url>>();
+        for uri in uris {
+            self.publish_diagnostics(uri, None).await;
+        }
+    }
+
+    /// Format the document, None when formatting would not change anything
+    async fn format(&self, uri: &Url) -> Option<String> {
+        let file = Self::to_markdown_file(uri, &self.document(uri).await?);
+        let configs = self.configs.clone();
+        match tokio::task::spawn_blocking(move || {
+            let formatted = checkmark_fmt::fmt_markdown(&file, &configs.get(&file));
+            (file, formatted)
+        })
+        .await
+        {
+            Ok((file, Ok(formatted))) if !file.content.eq(&formatted.content) => {
+                Some(formatted.content)
+            }
+            Ok((_, Ok(_))) => None,
+            Ok((_, Err(err))) => {
+                log::error!("{}", err);
+                None
+            }
+            Err(err) => {
+                log::error!("Unable to format {}: {}", uri, err);
+                None
+            }
+        }
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, params: InitializeParams) -> Result<InitializeResult> {
+        // Messages are rendered by editors, terminal colors would only add noise
+        colored::control::set_override(false);
+        let watch_config_files = params
+            .capabilities
+            .workspace
+            .and_then(|workspace| workspace.did_change_watched_files)
+            .and_then(|capability| capability.dynamic_registration)
+            .unwrap_or(false);
+        self.watch_config_files
+            .store(watch_config_files, Ordering::Relaxed);
+        Ok(InitializeResult {
+            server_info: Some(ServerInfo {
+                name: "checkmark".to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                    TextDocumentSyncKind::FULL,
+                )),
+                document_formatting_provider: Some(OneOf::Left(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Options(
+                    CodeActionOptions {
+                        code_action_kinds: Some(vec![
+                            CodeActionKind::QUICKFIX,
+                            CodeActionKind::SOURCE_FIX_ALL,
+                        ]),
+                        ..CodeActionOptions::default()
+                    },
+                )),
+                document_link_provider: Some(DocumentLinkOptions {
+                    resolve_provider: Some(false),
+                    work_done_progress_options: WorkDoneProgressOptions::default(),
+                }),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                ..ServerCapabilities::default()
+            },
+        })
+    }
+
+    async fn initialized(&self, _: InitializedParams) {
+        log::debug!("Language server initialized");
+        if !self.watch_config_files.load(Ordering::Relaxed) {
+            return;
+        }
+        // Config files are usually edited outside of the editor, or not opened at all
+        let watchers = common::CONFIG_FILE_NAMES
+            .iter()
+            .map(|name| FileSystemWatcher {
+                glob_pattern: GlobPattern::String(format!("**/{name}")),
+                kind: None,
+            })
+            .collect();
+        let registration = Registration {
+            id: "checkmark-config-files".to_string(),
+            method: "workspace/didChangeWatchedFiles".to_string(),
+            register_options: serde_json::to_value(DidChangeWatchedFilesRegistrationOptions {
+                watchers,
+            })
+            .ok(),
+        };
+        if let Err(err) = self.client.register_capability(vec![registration]).await {
+            log::warn!("Unable to watch config files: {}", err);
+        }
+    }
+
+    async fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents
+            .write()
+            .await
+            .insert(uri.clone(), params.text_document.text);
+        self.publish_diagnostics(uri, Some(params.text_document.version))
+            .await;
+    }
+
+    async fn did_change(&self, mut params: DidChangeTextDocumentParams) {
+        // Full synchronization, the last change always holds the whole document
+        if let Some(change) = params.content_changes.pop() {
+            let uri = params.text_document.uri;
+            self.documents
+                .write()
+                .await
+                .insert(uri.clone(), change.text);
+            self.publish_diagnostics(uri, Some(params.text_document.version))
+                .await;
+        }
+    }
+
+    async fn did_save(&self, params: DidSaveTextDocumentParams) {
+        if is_config_file(&params.text_document.uri) {
+            self.reload_configs().await;
+        } else {
+            self.publish_diagnostics(params.text_document.uri, None)
+                .await;
+        }
+    }
+
+    async fn did_change_watched_files(&self, params: DidChangeWatchedFilesParams) {
+        if params
+            .changes
+            .iter()
+            .any(|change| is_config_file(&change.uri))
+        {
+            self.reload_configs().await;
+        }
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+        self.documents.write().await.remove(&uri);
+        self.client.publish_diagnostics(uri, vec![], None).await;
+    }
+
+    async fn formatting(&self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        let uri = params.text_document.uri;
+        let Some(text) = self.document(&uri).await else {
+            return Ok(None);
+        };
+        Ok(self
+            .format(&uri)
+            .await
+            .map(|formatted| vec![TextEdit::new(full_range(&text), formatted)]))
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+        let Some(text) = self.document(&uri).await else {
+            return Ok(None);
+        };
+        let mut actions: CodeActionResponse = vec![];
+        for diagnostic in &params.context.diagnostics {
+            if !diagnostic.source.as_deref().eq(&Some(diagnostics::SOURCE)) {
+                continue;
+            }
+            for replacement in diagnostics::replacements(diagnostic) {
+                let edit = TextEdit::new(diagnostic.range, replacement.clone());
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Replace with {:#?}", &replacement),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit::new(HashMap::from([(
+                        uri.clone(),
+                        vec![edit],
+                    )]))),
+                    ..CodeAction::default()
+                }));
+            }
+        }
+        if let Some(formatted) = self.format(&uri).await {
+            let edit = TextEdit::new(full_range(&text), formatted);
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: "Format document with checkmark".to_string(),
+                kind: Some(CodeActionKind::SOURCE_FIX_ALL),
+                edit: Some(WorkspaceEdit::new(HashMap::from([(
+                    uri.clone(),
+                    vec![edit],
+                )]))),
+                ..CodeAction::default()
+            }));
+        }
+        Ok(Some(actions))
+    }
+
+    async fn document_link(&self, params: DocumentLinkParams) -> Result<Option<Vec<DocumentLink>>> {
+        let uri = params.text_document.uri;
+        Ok(self
+            .document(&uri)
+            .await
+            .map(|text| anchors::document_links(&uri, &text)))
+    }
+
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+        Ok(self
+            .document(&uri)
+            .await
+            .and_then(|text| anchors::hover(&text, position_to_offset(&text, &position))))
+    }
+}
+
+/// Language server service that tells when the client asks it to exit.
+/// Otherwise serving goes on until the input is closed, which editors do not always do
+struct ExitNotifier<S> {
+    inner: S,
+    exit: Arc<Notify>,
+}
+
+impl<S: Service<Request>> Service<Request> for ExitNotifier<S> {
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), S::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        let is_exit = request.method().eq("exit");
+        let response = self.inner.call(request);
+        if is_exit {
+            self.exit.notify_one();
+        }
+        response
+    }
+}
+
+/// Serve Language Server Protocol(JSON-RPC) over provided streams
+/// until client asks to exit. Use stdin/stdout for editors.
+/// Configs resolve the effective config of every document, whenever it is checked or formatted
+pub async fn serve<I, O, C>(input: I, output: O, configs: C)
+where
+    I: AsyncRead + Unpin,
+    O: AsyncWrite,
+    C: Configs + 'static,
+{
+    let (service, socket) = LspService::new(|client| Backend::new(client, Arc::new(configs)));
+    let exit = Arc::new(Notify::new());
+    let service = ExitNotifier {
+        inner: service,
+        exit: exit.clone(),
+    };
+    tokio::select! {
+        _ = Server::new(input, output, socket).serve(service) => {}
+        _ = exit.notified() => log::debug!("Language server exited"),
+    }
+}
@@ -0,0 +1,74 @@
+use tower_lsp::lsp_types::{Position, Range};
+
+/// Convert byte offset in the text into the LSP position.
+/// LSP counts lines from 0 and characters in UTF-16 code units
+pub fn offset_to_position(text: &str, offset: usize) -> Position {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    Position {
+        line: before.matches('\n').count() as u32,
+        character: before[line_start..].encode_utf16().count() as u32,
+    }
+}
+
+/// Convert LSP position into the byte offset in the text.
+/// Positions outside of the line or text are clamped to their end
+pub fn position_to_offset(text: &str, position: &Position) -> usize {
+    let line_start = text
+        .split_inclusive('\n')
+        .take(position.line as usize)
+        .map(str::len)
+        .sum::<usize>();
+    let line = text[line_start..].split('\n').next().unwrap_or("");
+    let mut num_utf16_units = 0;
+    for (index, c) in line.char_indices() {
+        if num_utf16_units >= position.character as usize {
+            return line_start + index;
+        }
+        num_utf16_units += c.len_utf16();
+    }
+    line_start + line.len()
+}
+
+/// Convert byte offsets in the text into the LSP range
+pub fn offsets_to_range(text: &str, start: usize, end: usize) -> Range {
+    Range {
+        start: offset_to_position(text, start),
+        end: offset_to_position(text, end),
+    }
+}
+
+/// Range that covers the whole text
+pub fn full_range(text: &str) -> Range {
+    offsets_to_range(text, 0, text.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn offset_to_position_counts_utf16() {
+        let text = "# Título\n\n😀 emoji";
+        assert_eq!(offset_to_position(text, 0), Position::new(0, 0));
+        assert_eq!(offset_to_position(text, 9), Position::new(0, 8));
+        assert_eq!(offset_to_position(text, 10), Position::new(1, 0));
+        assert_eq!(offset_to_position(text, 16), Position::new(2, 3));
+        assert_eq!(offset_to_position(text, 100), Position::new(2, 8));
+    }
+
+    #[test]
+    fn position_to_offset_counts_utf16() {
+        let text = "# Título\n\n😀 emoji";
+        assert_eq!(position_to_offset(text, &Position::new(0, 8)), 9);
+        assert_eq!(position_to_offset(text, &Position::new(0, 3)), 3);
+        assert_eq!(position_to_offset(text, &Position::new(2, 3)), 16);
+        assert_eq!(position_to_offset(text, &Position::new(2, 100)), text.len());
+        assert_eq!(position_to_offset(text, &Position::new(10, 0)), text.len());
+    }
+}
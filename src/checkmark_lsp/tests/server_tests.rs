@@ -0,0 +1,306 @@
+use pretty_assertions::assert_eq;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, DuplexStream};
+
+const URI: &str = "file:///tmp/checkmark/README.md";
+
+/// Minimal in-process LSP client that talks to the server over in-memory pipes
+struct Client {
+    reader: BufReader<DuplexStream>,
+    writer: DuplexStream,
+    next_id: u64,
+    server: tokio::task::JoinHandle<()>,
+}
+
+impl Client {
+    async fn start(config: common::Config) -> Self {
+        Self::start_with(move |_: &common::MarkDownFile| config.clone()).await
+    }
+
+    async fn start_with(configs: impl checkmark_lsp::Configs + 'static) -> Self {
+        let (client_write, server_read) = tokio::io::duplex(1024 * 1024);
+        let (server_write, client_read) = tokio::io::duplex(1024 * 1024);
+        let server = tokio::spawn(checkmark_lsp::serve(server_read, server_write, configs));
+        let mut client = Self {
+            reader: BufReader::new(client_read),
+            writer: client_write,
+            next_id: 0,
+            server,
+        };
+        client
+            .request("initialize", json!({ "capabilities": {} }))
+            .await;
+        client.notify("initialized", json!({})).await;
+        client
+    }
+
+    async fn send(&mut self, message: Value) {
+        let body = message.to_string();
+        let frame = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+        self.writer.write_all(frame.as_bytes()).await.unwrap();
+    }
+
+    async fn receive(&mut self) -> Value {
+        let mut content_length = 0;
+        loop {
+            let mut line = String::new();
+            self.reader.read_line(&mut line).await.unwrap();
+            let line = line.trim();
+            if line.is_empty() {
+                break;
+            }
+            if let Some(length) = line.strip_prefix("Content-Length: ") {
+                content_length = length.parse().unwrap();
+            }
+        }
+        let mut body = vec![0; content_length];
+        self.reader.read_exact(&mut body).await.unwrap();
+        serde_json::from_slice(&body).unwrap()
+    }
+
+    async fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+            .await;
+    }
+
+    /// Send request and wait for its result, skipping server notifications
+    async fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = self.next_id;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }))
+            .await;
+        loop {
+            let message = self.receive().await;
+            if message["id"] == json!(id) {
+                return message["result"].clone();
+            }
+        }
+    }
+
+    /// Wait for the next notification with published diagnostics
+    async fn diagnostics(&mut self) -> Vec<Value> {
+        loop {
+            let message = self.receive().await;
+            if message["method"] == "textDocument/publishDiagnostics" {
+                return message["params"]["diagnostics"].as_array().unwrap().clone();
+            }
+        }
+    }
+
+    async fn open(&mut self, text: &str) {
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": {
+                    "uri": URI,
+                    "languageId": "markdown",
+                    "version": 1,
+                    "text": text
+                }
+            }),
+        )
+        .await;
+    }
+}
+
+fn codes(diagnostics: &[Value]) -> Vec<String> {
+    let mut codes = diagnostics
+        .iter()
+        .map(|diagnostic| diagnostic["code"].as_str().unwrap().to_string())
+        .collect::<Vec<String>>();
+    codes.sort();
+    codes
+}
+
+#[tokio::test]
+async fn diagnostics_published_on_open_and_change() {
+    let mut client = Client::start(common::Config::default()).await;
+
+    client.open("# Title\n\n### Section\n").await;
+    let diagnostics = client.diagnostics().await;
+    assert_eq!(codes(&diagnostics), vec!["MD001"]);
+    assert_eq!(
+        diagnostics[0]["range"],
+        json!({ "start": { "line": 2, "character": 0 }, "end": { "line": 2, "character": 11 } })
+    );
+    assert_eq!(diagnostics[0]["source"], "checkmark");
+
+    client
+        .notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": URI, "version": 2 },
+                "contentChanges": [{ "text": "# Title\n\n## Section\n" }]
+            }),
+        )
+        .await;
+    assert_eq!(client.diagnostics().await, Vec::<Value>::new());
+
+    client
+        .notify(
+            "textDocument/didClose",
+            json!({ "textDocument": { "uri": URI } }),
+        )
+        .await;
+    assert_eq!(client.diagnostics().await, Vec::<Value>::new());
+}
+
+#[tokio::test]
+async fn spelling_suggestions_offered_as_quick_fixes() {
+    let mut client = Client::start(common::Config::default()).await;
+
+    client.open("# Title\n\nSome headr\n").await;
+    let diagnostics = client.diagnostics().await;
+    assert_eq!(codes(&diagnostics), vec!["Spelling"]);
+
+    let actions = client
+        .request(
+            "textDocument/codeAction",
+            json!({
+                "textDocument": { "uri": URI },
+                "range": diagnostics[0]["range"],
+                "context": { "diagnostics": diagnostics }
+            }),
+        )
+        .await;
+    let action = &actions.as_array().unwrap()[0];
+    assert_eq!(action["kind"], "quickfix");
+    let replacement = action["edit"]["changes"][URI][0]["newText"]
+        .as_str()
+        .unwrap();
+    assert_eq!(action["title"], format!("Replace with {:#?}", replacement));
+    assert_eq!(
+        action["edit"]["changes"][URI][0]["range"],
+        json!({ "start": { "line": 2, "character": 5 }, "end": { "line": 2, "character": 10 } })
+    );
+}
+
+#[tokio::test]
+async fn formatting_replaces_whole_document() {
+    let mut client = Client::start(common::Config::default()).await;
+
+    client.open("# Title\n\n\n\nText   \n").await;
+    client.diagnostics().await;
+
+    let edits = client
+        .request(
+            "textDocument/formatting",
+            json!({
+                "textDocument": { "uri": URI },
+                "options": { "tabSize": 4, "insertSpaces": true }
+            }),
+        )
+        .await;
+    assert_eq!(
+        edits,
+        json!([{
+            "range": { "start": { "line": 0, "character": 0 }, "end": { "line": 5, "character": 0 } },
+            "newText": "# Title\n\nText\n"
+        }])
+    );
+}
+
+#[tokio::test]
+async fn formatting_of_formatted_document_does_nothing() {
+    let mut client = Client::start(common::Config::default()).await;
+
+    client.open("# Title\n\nText\n").await;
+    client.diagnostics().await;
+
+    let edits = client
+        .request(
+            "textDocument/formatting",
+            json!({
+                "textDocument": { "uri": URI },
+                "options": { "tabSize": 4, "insertSpaces": true }
+            }),
+        )
+        .await;
+    assert_eq!(edits, Value::Null);
+}
+
+#[tokio::test]
+async fn document_links_and_hover_for_heading_anchors() {
+    let mut client = Client::start(common::Config::default()).await;
+
+    client
+        .open("# Title\n\nSee [about](#about-us).\n\n## About us\n")
+        .await;
+    client.diagnostics().await;
+
+    let links = client
+        .request(
+            "textDocument/documentLink",
+            json!({ "textDocument": { "uri": URI } }),
+        )
+        .await;
+    assert_eq!(links[0]["target"], format!("{}#L5", URI));
+
+    let hover = client
+        .request(
+            "textDocument/hover",
+            json!({
+                "textDocument": { "uri": URI },
+                "position": { "line": 2, "character": 6 }
+            }),
+        )
+        .await;
+    assert_eq!(
+        hover["contents"]["value"],
+        "Points to heading **About us** on line 5"
+    );
+}
+
+/// Disables MD001 once config files have changed
+struct ChangingConfigs {
+    changed: Arc<AtomicBool>,
+}
+
+impl checkmark_lsp::Configs for ChangingConfigs {
+    fn get(&self, _: &common::MarkDownFile) -> common::Config {
+        let mut config = common::Config::default();
+        if self.changed.load(Ordering::SeqCst) {
+            config.linter.disabled_rules = vec![String::from("MD001")];
+        }
+        config
+    }
+
+    fn invalidate(&self) {
+        self.changed.store(true, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn opened_documents_rechecked_when_config_files_change() {
+    let changed = Arc::new(AtomicBool::new(false));
+    let mut client = Client::start_with(ChangingConfigs {
+        changed: changed.clone(),
+    })
+    .await;
+
+    client.open("# Title\n\n### Section\n").await;
+    assert_eq!(codes(&client.diagnostics().await), vec!["MD001"]);
+
+    client
+        .notify(
+            "workspace/didChangeWatchedFiles",
+            json!({ "changes": [{ "uri": "file:///tmp/checkmark/checkmark.toml", "type": 2 }] }),
+        )
+        .await;
+    assert_eq!(client.diagnostics().await, Vec::<Value>::new());
+    assert!(changed.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn server_stops_on_exit_while_input_is_open() {
+    let mut client = Client::start(common::Config::default()).await;
+
+    client.request("shutdown", Value::Null).await;
+    client.notify("exit", Value::Null).await;
+    tokio::time::timeout(std::time::Duration::from_secs(10), &mut client.server)
+        .await
+        .expect("server keeps running after exit")
+        .unwrap();
+}
@@ -1,7 +1,8 @@
 mod spell_checker;
 mod text_to_words;
 
-use spell_checker::{check_spelling, create_spell_checker};
+use spell_checker::check_spelling;
+pub use spell_checker::create_spell_checker;
 use symspell::{AsciiStringStrategy, SymSpell};
 use text_to_words::{text_to_words, Word};
 
@@ -11,6 +12,29 @@ use common::{CheckIssue, CheckIssueBuilder, IssueCategory, IssueSeverity, MarkDo
 use rayon::prelude::*;
 use std::sync::{Arc, Mutex};
 
+/// Spell checker with loaded dictionaries, see `create_spell_checker()`
+pub type SpellChecker = SymSpell<AsciiStringStrategy>;
+
+/// Suggestions are always lowercase, apply the case
+/// of the original word to them, e.g. "Headr" -> "Head"
+fn match_case(original: &str, suggestion: &str) -> String {
+    let mut chars = original.chars();
+    match chars.next() {
+        Some(first) if first.is_uppercase() => {
+            if original.chars().count() > 1 && chars.all(|c| c.is_uppercase()) {
+                suggestion.to_uppercase()
+            } else {
+                let mut suggestion_chars = suggestion.chars();
+                match suggestion_chars.next() {
+                    Some(s) => s.to_uppercase().chain(suggestion_chars).collect(),
+                    None => String::new(),
+                }
+            }
+        }
+        _ => suggestion.to_string(),
+    }
+}
+
 fn to_check_issue(
     word: &Word,
     source_file_path: &str,
@@ -40,6 +64,7 @@ fn to_check_issue(
                 &word.source.value, suggestion.term
             );
             issue = issue.push_fix(&format!("💡 {} {}", "Suggestion".cyan(), fix));
+            issue = issue.push_replacement(&match_case(&word.source.value, &suggestion.term));
         }
     }
     if let Some(location) = &config_file_location {
@@ -71,7 +96,7 @@ fn to_check_issue(
 /// https://github.com/reneklacan/symspell
 /// https://github.com/wolfgarbe/SymSpell
 pub fn spell_check(
-    spell_checker: &SymSpell<AsciiStringStrategy>,
+    spell_checker: &SpellChecker,
     file: &MarkDownFile,
     config: &common::Config,
 ) -> Vec<CheckIssue> {
@@ -101,6 +126,15 @@ pub fn spell_check_bulk(
     });
 }
 
+/// Words of the file that are unknown to the spell checker, in order of appearance
+pub fn unknown_words(spell_checker: &SpellChecker, file: &MarkDownFile) -> Vec<String> {
+    text_to_words(&file.content)
+        .par_iter()
+        .filter(|word| check_spelling(spell_checker, &word.value).is_err())
+        .map(|word| word.value.clone())
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -167,6 +201,10 @@ mod test {
                 actual_issues.get(index).unwrap().offset_end
             );
             assert_eq!(issue.message, actual_issues.get(index).unwrap().message);
+            assert_eq!(
+                issue.replacements,
+                actual_issues.get(index).unwrap().replacements
+            );
         }
     }
 
@@ -185,7 +223,7 @@ mod test {
             file_path: DUMMY_FILE_PATH.to_owned(),
             row_num_start: 1,
             row_num_end: 1,
-            col_num_start: 3,
+            col_num_start: 13,
             col_num_end: 18,
             offset_start: 12,
             offset_end: 17,
@@ -196,6 +234,7 @@ mod test {
                 "💡 Consider white-listing this word by adding it to the \"words_whitelist\" property in the config file".to_string(),
                 "🔗 https://github.com/vvvar/checkmark/tree/main#generate-config".to_string()
             ],
+            replacements: vec!["head".to_string()],
         },
     ]);
     }
@@ -209,8 +248,8 @@ mod test {
             file_path: DUMMY_FILE_PATH.to_owned(),
             row_num_start: 3,
             row_num_end: 3,
-            col_num_start: 1,
-            col_num_end: 27,
+            col_num_start: 9,
+            col_num_end: 14,
             offset_start: 10,
             offset_end: 15,
             message: "\"sommm\": Unknown word".to_string(),
@@ -220,6 +259,7 @@ mod test {
                 "💡 Consider white-listing this word by adding it to the \"words_whitelist\" property in the config file".to_string(),
                 "🔗 https://github.com/vvvar/checkmark/tree/main#generate-config".to_string()
             ],
+            replacements: vec!["somme".to_string()],
         },
         common::CheckIssue {
             category: common::IssueCategory::Spelling,
@@ -227,8 +267,8 @@ mod test {
             file_path: DUMMY_FILE_PATH.to_owned(),
             row_num_start: 3,
             row_num_end: 3,
-            col_num_start: 1,
-            col_num_end: 27,
+            col_num_start: 15,
+            col_num_end: 23,
             offset_start: 16,
             offset_end: 24,
             message: "\"additnal\": Unknown word".to_string(),
@@ -238,10 +278,33 @@ mod test {
                 "💡 Consider white-listing this word by adding it to the \"words_whitelist\" property in the config file".to_string(),
                 "🔗 https://github.com/vvvar/checkmark/tree/main#generate-config".to_string()
             ],
+            replacements: vec!["additional".to_string()],
         }
     ]);
     }
 
+    #[test]
+    fn spelling_same_misspelled_word_reported_at_each_occurrence() {
+        let content = "Some headr\nand another headr\n";
+        let markdown = common::MarkDownFile {
+            path: DUMMY_FILE_PATH.to_owned(),
+            content: content.to_owned(),
+            issues: vec![],
+        };
+        let issues = spell_check(&SPELL_CHECKER, &markdown, &common::Config::default());
+        let positions = issues
+            .iter()
+            .map(|issue| {
+                (
+                    issue.row_num_start,
+                    issue.col_num_start,
+                    &content[issue.offset_start..issue.offset_end],
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(positions, vec![(1, 6, "headr"), (2, 13, "headr")]);
+    }
+
     #[test]
     fn spelling_apostrophe_supported() {
         assert_has_no_issues("# Don't", &vec![]);
@@ -273,7 +336,7 @@ mod test {
         row_num_start: 1,
         row_num_end: 1,
         col_num_start: 3,
-        col_num_end: 17,
+        col_num_end: 15,
         offset_start: 2,
         offset_end: 14,
         message: "\"fdssryyukiuu\": Unknown word".to_string(),
@@ -282,6 +345,7 @@ mod test {
             "💡 Consider white-listing this word by adding it to the \"words_whitelist\" property in the config file".to_string(),
             "🔗 https://github.com/vvvar/checkmark/tree/main#generate-config".to_string()
         ],
+        replacements: vec![],
     },]);
     }
 
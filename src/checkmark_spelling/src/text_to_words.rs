@@ -1,6 +1,4 @@
-use common::find_index;
 use markdown::mdast::Text;
-use markdown::unist::Position;
 use rayon::prelude::*;
 use std::fmt::{Display, Formatter, Result};
 use std::ops::Range;
@@ -86,9 +84,53 @@ impl PartialEq<&str> for Word {
     }
 }
 
+/// Return byte offset of the sub-slice relative to the beginning of the parent string.
+/// Sub-slice must be produced from the parent(by slicing or splitting it)
+fn offset_within(parent: &str, sub: &str) -> usize {
+    sub.as_ptr() as usize - parent.as_ptr() as usize
+}
+
+/// Byte offsets where lines of the text start, to find line and column of an offset without rescanning the text
+struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    fn new(text: &str) -> Self {
+        Self {
+            line_starts: std::iter::once(0)
+                .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+                .collect(),
+        }
+    }
+
+    /// Return line and column(both start at 1) of the byte offset in the text
+    fn line_and_column(&self, offset: usize) -> (usize, usize) {
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        (line, offset - self.line_starts[line - 1] + 1)
+    }
+}
+
 // Takes a Text node and splits it into words that is ready for spell checking
-// while preserving meta information about original word and its position
-fn extract(node: &markdown::mdast::Text) -> Vec<Word> {
+// while preserving meta information about original word and its position.
+// Source is a document from which the node was parsed, it is used only
+// when node has a position, otherwise positions are relative to the node value.
+// Index is the line index of the source
+fn extract(node: &markdown::mdast::Text, source: &str, index: &LineIndex) -> Vec<Word> {
+    let node_index;
+    let (document, region, index) = match &node.position {
+        Some(position) => (source, position.start.offset..position.end.offset, index),
+        None => {
+            node_index = LineIndex::new(&node.value);
+            (node.value.as_str(), 0..node.value.len(), &node_index)
+        }
+    };
+    // Value of the text node may differ from the source(escapes, entities,
+    // block quote markers and indentation are stripped by the parser), but
+    // the source is never shorter than the value. So each word is located in
+    // the source no earlier than its offset in the value and no earlier
+    // than the end of the previously located word
+    let mut cursor = region.start;
     node.value
         .split_ascii_whitespace()
         .filter(|word| !is_url::is_url(word))
@@ -118,54 +160,44 @@ fn extract(node: &markdown::mdast::Text) -> Vec<Word> {
         .map(|w| (w, w.to_lowercase()))
         .filter(|(_, escaped)| !escaped.is_empty())
         .filter(|(_, escaped)| !is_ignored_word(escaped))
-        .map(|(original, escaped)| {
-            let fallback_position = Position::new(0, 0, 0, 0, 0, 0);
-            let text_node_offset = Range {
-                start: node
-                    .position
-                    .as_ref()
-                    .unwrap_or(&fallback_position)
-                    .start
-                    .offset,
-                end: node
-                    .position
-                    .as_ref()
-                    .unwrap_or(&fallback_position)
-                    .end
-                    .offset,
+        .filter_map(|(original, escaped)| {
+            let mut search_from =
+                (region.start + offset_within(&node.value, original)).clamp(cursor, region.end);
+            while !document.is_char_boundary(search_from) {
+                search_from += 1;
+            }
+            let Some(found) = document[search_from..region.end].find(original) else {
+                log::debug!(
+                    "Unable to locate word {:#?} in the source, skipping it",
+                    original
+                );
+                return None;
             };
-            let word_offset = find_index(&node.value, original);
-            Word {
+            let (start, end) = (search_from + found, search_from + found + original.len());
+            cursor = end;
+            let (line_start, column_start) = index.line_and_column(start);
+            let (line_end, column_end) = index.line_and_column(end);
+            Some(Word {
                 value: escaped.to_string(),
                 source: WordSource {
                     value: original.to_string(),
-                    line: node
-                        .position
-                        .as_ref()
-                        .map(|p| p.start.line..p.end.line)
-                        .unwrap_or(0..0),
-                    column: node
-                        .position
-                        .as_ref()
-                        .map(|p| p.start.column..p.end.column)
-                        .unwrap_or(0..0),
-                    offset: Range {
-                        start: text_node_offset.start + word_offset.start,
-                        end: text_node_offset.start + word_offset.end,
-                    },
+                    line: line_start..line_end,
+                    column: column_start..column_end,
+                    offset: start..end,
                 },
-            }
+            })
         })
         .collect::<Vec<_>>()
 }
 
 pub fn text_to_words(text: &str) -> Vec<Word> {
     let ast = common::ast::parse(text).unwrap();
+    let index = LineIndex::new(text);
     common::ast::BfsIterator::from(&ast)
         .filter_map(|n| common::ast::try_cast_to_text(n))
         .collect::<Vec<&Text>>() // Need to collect because .par_iter() is not available for iterators
         .par_iter()
-        .flat_map(|t| extract(t))
+        .flat_map(|t| extract(t, text, &index))
         .collect::<Vec<Word>>()
 }
 
@@ -174,6 +206,10 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    fn extract_from_node(value: &str) -> Vec<Word> {
+        extract(&text_node(value), "", &LineIndex::new(""))
+    }
+
     fn text_node(value: &str) -> markdown::mdast::Text {
         markdown::mdast::Text {
             value: value.to_string(),
@@ -216,18 +252,67 @@ mod tests {
     #[test]
     fn extracting_words_from_text_node() {
         assert_eq!(
-            extract(&text_node("This/is a & {test} 111+")),
+            extract_from_node("This/is a & {test} 111+"),
             vec!["this", "is", "a", "test"]
         );
         assert_eq!(
-            extract(&text_node(
-                "Get, https://totalbs.com your [double-edged]: sword."
-            )),
+            extract_from_node("Get, https://totalbs.com your [double-edged]: sword."),
             vec!["get", "your", "double", "edged", "sword"]
         );
         assert_eq!(
-            extract(&text_node("Hello(there)World fr1end 4real!?")),
+            extract_from_node("Hello(there)World fr1end 4real!?"),
             vec!["hello", "there", "world", "4real"]
         );
     }
+
+    #[test]
+    fn word_positions_in_text_node() {
+        let words = extract_from_node("teh cat and teh dog\nteh end");
+        let positions = words
+            .iter()
+            .filter(|w| w.value == "teh")
+            .map(|w| {
+                (
+                    w.source.offset.clone(),
+                    w.source.line.clone(),
+                    w.source.column.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            positions,
+            vec![
+                (0..3, 1..1, 1..4),
+                (12..15, 1..1, 13..16),
+                (20..23, 2..2, 1..4)
+            ]
+        );
+    }
+
+    #[test]
+    fn word_positions_in_source() {
+        let source = "# Title\n\n> Quoted \\[teh\\]\n> and teh\n";
+        let words = text_to_words(source);
+        let positions = words
+            .iter()
+            .filter(|w| w.value == "teh")
+            .map(|w| {
+                (
+                    &source[w.source.offset.clone()],
+                    w.source.line.clone(),
+                    w.source.column.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(positions, vec![("teh", 3..3, 12..15), ("teh", 4..4, 7..10)]);
+    }
+
+    #[test]
+    fn line_and_column_of_offset() {
+        let index = LineIndex::new("ab\ncd\n\ne");
+        assert_eq!(index.line_and_column(0), (1, 1));
+        assert_eq!(index.line_and_column(2), (1, 3));
+        assert_eq!(index.line_and_column(3), (2, 1));
+        assert_eq!(index.line_and_column(7), (4, 1));
+    }
 }
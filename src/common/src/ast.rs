@@ -1,4 +1,6 @@
-use markdown::mdast::{BlockQuote, Code, Heading, Html, Link, List, ListItem, Node, Strong, Text};
+use markdown::mdast::{
+    BlockQuote, Break, Code, Heading, Html, Link, List, ListItem, Node, Strong, Text,
+};
 
 #[derive(Debug)]
 pub struct BfsIterator<'a> {
@@ -187,6 +189,24 @@ pub fn try_cast_to_code(node: &Node) -> Option<&Code> {
     }
 }
 
+/// Return the break node if the provided generic node is a hard break.
+/// Meant to be used in a filter_map statement to filter break nodes
+/// from a generic AST.
+/// Example:
+/// ```
+/// # use markdown::mdast::{Break, Node};
+/// let ast = common::ast::parse("Line\\\nbreak").unwrap();
+/// let breaks = common::ast::BfsIterator::from(&ast)
+///                  .filter_map(|n| common::ast::try_cast_to_break(n))
+///                  .collect::<Vec<&Break>>();
+/// ```
+pub fn try_cast_to_break(node: &Node) -> Option<&Break> {
+    match node {
+        Node::Break(e) => Some(e),
+        _ => None,
+    }
+}
+
 /// Return the block quote node if the provided generic node is a block quote.
 /// Meant to be used in a filter_map statement to filter block quote nodes
 /// from a generic AST.
@@ -205,6 +225,35 @@ pub fn try_cast_to_block_quote(node: &Node) -> Option<&BlockQuote> {
     }
 }
 
+/// Takes heading and returns fragment link of it, e.g. "#heading-name".
+/// Link element that want to jump to this header should use this fragment.
+/// Follows the GitHub heading algorithm: convert heading to lowercase,
+/// remove punctuation and convert spaces to dashes
+/// Example:
+/// ```
+/// let ast = common::ast::parse("# Seek & Destroy").unwrap();
+/// let heading = common::ast::BfsIterator::from(&ast)
+///                  .find_map(|n| common::ast::try_cast_to_heading(n))
+///                  .unwrap();
+/// assert_eq!(common::ast::heading_to_fragment(heading), "#seek--destroy");
+/// ```
+pub fn heading_to_fragment(heading: &Heading) -> String {
+    let mut text = "".to_string();
+    for node in &heading.children {
+        if let Node::Text(t) = node {
+            text = format!("{}{}", &text, &t.value);
+        }
+    }
+    text = format!(
+        "#{}",
+        &text
+            .to_lowercase()
+            .replace([',', '.', '+', '&'], "")
+            .replace(' ', "-")
+    );
+    text
+}
+
 /// Parse Markdown file into an AST
 pub fn parse(source: &str) -> Result<Node, markdown::message::Message> {
     let options = markdown::ParseOptions {
@@ -216,3 +265,30 @@ pub fn parse(source: &str) -> Result<Node, markdown::message::Message> {
     };
     markdown::to_mdast(source, &options)
 }
+
+/// Parse the file into an AST according to its flavor: MDX files get JSX, ESM and expressions
+/// instead of HTML, autolinks and indented code, like MDX compilers do
+pub fn parse_file(file: &crate::MarkDownFile) -> Result<Node, markdown::message::Message> {
+    match file.file_type() {
+        crate::FileType::Markdown => parse(&file.content),
+        crate::FileType::Mdx => {
+            let options = markdown::ParseOptions {
+                constructs: markdown::Constructs {
+                    frontmatter: true,
+                    autolink: false,
+                    code_indented: false,
+                    html_flow: false,
+                    html_text: false,
+                    mdx_esm: true,
+                    mdx_expression_flow: true,
+                    mdx_expression_text: true,
+                    mdx_jsx_flow: true,
+                    mdx_jsx_text: true,
+                    ..markdown::Constructs::gfm()
+                },
+                ..markdown::ParseOptions::gfm()
+            };
+            markdown::to_mdast(&file.content, &options)
+        }
+    }
+}
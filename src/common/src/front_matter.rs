@@ -0,0 +1,304 @@
+use crate::{Config, MarkDownFile};
+use markdown::unist::Position;
+use yaml_rust2::{Yaml, YamlLoader};
+
+/// Language front matter is written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Delimited by `---`
+    Yaml,
+    /// Delimited by `+++`
+    Toml,
+}
+
+/// Front matter of the document
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrontMatter {
+    pub format: Format,
+    /// Source without delimiters
+    pub raw: String,
+    /// Parsed source, dates of TOML are strings, i.e. "2024-01-31"
+    pub value: serde_json::Value,
+    /// Where the front matter is in the document, including delimiters
+    pub position: Position,
+}
+
+impl FrontMatter {
+    /// Parse front matter of the document, None when there is none.
+    /// Front matter that is not valid YAML or TOML is an error
+    pub fn from_content(content: &str) -> Result<Option<Self>, String> {
+        // Front matter could be only at the very beginning, don't parse documents without it
+        if !content.starts_with("---") && !content.starts_with("+++") {
+            return Ok(None);
+        }
+        let Some(node) = crate::ast::parse(content)
+            .ok()
+            .and_then(|ast| ast.children()?.first().cloned())
+        else {
+            return Ok(None);
+        };
+        let (format, raw, position) = match node {
+            markdown::mdast::Node::Yaml(yaml) => (Format::Yaml, yaml.value, yaml.position),
+            markdown::mdast::Node::Toml(toml) => (Format::Toml, toml.value, toml.position),
+            _ => return Ok(None),
+        };
+        let value = match format {
+            Format::Yaml => YamlLoader::load_from_str(&raw)
+                .map_err(|err| err.to_string())?
+                .first()
+                .map(yaml_to_json)
+                .unwrap_or(serde_json::Value::Null),
+            Format::Toml => toml::from_str::<toml::Table>(&raw)
+                .map(|table| toml_to_json(&toml::Value::Table(table)))
+                .map_err(|err| err.message().to_string())?,
+        };
+        Ok(Some(Self {
+            format,
+            raw,
+            value,
+            position: position.unwrap_or(Position::new(1, 1, 0, 1, 1, 0)),
+        }))
+    }
+
+    /// Line where the top-level key is defined, i.e. "title: Guide".
+    /// None when the key is not defined on a line of its own
+    pub fn key_position(&self, key: &str, content: &str) -> Option<Position> {
+        let separator = match self.format {
+            Format::Yaml => ':',
+            Format::Toml => '=',
+        };
+        let index = self.raw.lines().position(|line| {
+            line.strip_prefix(key)
+                .or_else(|| line.strip_prefix(&format!("\"{}\"", key)))
+                .or_else(|| line.strip_prefix(&format!("'{}'", key)))
+                .is_some_and(|rest| rest.trim_start().starts_with(separator))
+        })?;
+        let length = self.raw.lines().nth(index)?.len();
+        // Raw source starts on the line after the opening delimiter
+        let line = self.position.start.line + 1 + index;
+        let offset = crate::find_offset_by_line_number(content, line - 1);
+        Some(Position::new(
+            line,
+            1,
+            offset,
+            line,
+            length + 1,
+            offset + length,
+        ))
+    }
+}
+
+/// Raw YAML front matter of the document(without `---` delimiters), None when there is none
+pub fn yaml(content: &str) -> Option<String> {
+    match FrontMatter::from_content(content) {
+        Ok(Some(front_matter)) if front_matter.format == Format::Yaml => Some(front_matter.raw),
+        _ => None,
+    }
+}
+
+/// Front matter of the document as a JSON value, None when there is none
+pub fn parse(content: &str) -> Result<Option<serde_json::Value>, String> {
+    Ok(FrontMatter::from_content(content)?.map(|front_matter| front_matter.value))
+}
+
+fn yaml_to_json(yaml: &Yaml) -> serde_json::Value {
+    match yaml {
+        Yaml::Real(real) => real
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::String(real.clone())),
+        Yaml::Integer(integer) => serde_json::Value::from(*integer),
+        Yaml::String(string) => serde_json::Value::String(string.clone()),
+        Yaml::Boolean(boolean) => serde_json::Value::Bool(*boolean),
+        Yaml::Array(array) => serde_json::Value::Array(array.iter().map(yaml_to_json).collect()),
+        Yaml::Hash(hash) => serde_json::Value::Object(
+            hash.iter()
+                .map(|(key, value)| {
+                    let key = match yaml_to_json(key) {
+                        serde_json::Value::String(key) => key,
+                        key => key.to_string(),
+                    };
+                    (key, yaml_to_json(value))
+                })
+                .collect(),
+        ),
+        Yaml::Alias(_) | Yaml::Null | Yaml::BadValue => serde_json::Value::Null,
+    }
+}
+
+fn toml_to_json(toml: &toml::Value) -> serde_json::Value {
+    match toml {
+        toml::Value::String(string) => serde_json::Value::String(string.clone()),
+        toml::Value::Integer(integer) => serde_json::Value::from(*integer),
+        toml::Value::Float(float) => serde_json::Number::from_f64(*float)
+            .map(serde_json::Value::Number)
+            .unwrap_or(serde_json::Value::Null),
+        toml::Value::Boolean(boolean) => serde_json::Value::Bool(*boolean),
+        toml::Value::Datetime(datetime) => serde_json::Value::String(datetime.to_string()),
+        toml::Value::Array(array) => {
+            serde_json::Value::Array(array.iter().map(toml_to_json).collect())
+        }
+        toml::Value::Table(table) => serde_json::Value::Object(
+            table
+                .iter()
+                .map(|(key, value)| (key.clone(), toml_to_json(value)))
+                .collect(),
+        ),
+    }
+}
+
+/// Either a plain switch of the check or its settings, which keep the check enabled
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(untagged)]
+pub enum Toggle<T> {
+    Enabled(bool),
+    Settings(T),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LintSettings {
+    /// Rules to skip, i.e. ["MD033"]
+    #[serde(default)]
+    pub disable: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SpellingSettings {
+    /// Words to ignore in addition to the configured ones
+    #[serde(default)]
+    pub words: Vec<String>,
+}
+
+/// Settings of a single document from the `checkmark` key of its front matter, i.e.
+/// `checkmark: { lint: { disable: [MD033] }, spelling: { words: [kubectl] }, linkcheck: false }`
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileConfig {
+    pub lint: Option<Toggle<LintSettings>>,
+    pub spelling: Option<Toggle<SpellingSettings>>,
+    pub linkcheck: Option<bool>,
+}
+
+impl FileConfig {
+    /// Settings from the front matter of the file, None when it doesn't have any
+    pub fn from_file(file: &MarkDownFile) -> Result<Option<Self>, String> {
+        let Some(mut front_matter) = parse(&file.content)? else {
+            return Ok(None);
+        };
+        match front_matter
+            .get_mut("checkmark")
+            .map(serde_json::Value::take)
+        {
+            Some(settings) if !settings.is_null() => serde_json::from_value(settings)
+                .map(Some)
+                .map_err(|err| format!("invalid \"checkmark\" settings: {}", err)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Apply settings on top of the config of the file.
+    /// Disabled rules and ignored words are added to the configured ones
+    pub fn apply(&self, config: &mut Config) {
+        match &self.lint {
+            Some(Toggle::Enabled(enabled)) => config.linter.skip = !enabled,
+            Some(Toggle::Settings(lint)) => config
+                .linter
+                .disabled_rules
+                .extend(lint.disable.iter().cloned()),
+            None => {}
+        }
+        match &self.spelling {
+            Some(Toggle::Enabled(enabled)) => config.spelling.skip = !enabled,
+            Some(Toggle::Settings(spelling)) => config
+                .spelling
+                .words_whitelist
+                .extend(spelling.words.iter().cloned()),
+            None => {}
+        }
+        if let Some(enabled) = self.linkcheck {
+            config.link_checker.skip = !enabled;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(content: &str) -> MarkDownFile {
+        MarkDownFile {
+            path: "README.md".to_string(),
+            content: content.to_string(),
+            issues: vec![],
+        }
+    }
+
+    #[test]
+    fn yaml_and_toml_front_matter() {
+        let content = "---\ntitle: Guide\nlast_reviewed: 2024-01-31\ntags: [a, b]\n---\n# Guide\n";
+        let front_matter = FrontMatter::from_content(content).unwrap().unwrap();
+        assert_eq!(front_matter.format, Format::Yaml);
+        assert_eq!(
+            front_matter.value,
+            serde_json::json!({"title": "Guide", "last_reviewed": "2024-01-31", "tags": ["a", "b"]})
+        );
+        let position = front_matter.key_position("last_reviewed", content).unwrap();
+        assert_eq!((position.start.line, position.start.offset), (3, 17));
+        assert_eq!(position.end.column, 26);
+
+        let content = "+++\ntitle = \"Guide\"\nlast_reviewed = 2024-01-31\n+++\n# Guide\n";
+        let front_matter = FrontMatter::from_content(content).unwrap().unwrap();
+        assert_eq!(front_matter.format, Format::Toml);
+        assert_eq!(
+            front_matter.value,
+            serde_json::json!({"title": "Guide", "last_reviewed": "2024-01-31"})
+        );
+        assert_eq!(
+            front_matter
+                .key_position("last_reviewed", content)
+                .unwrap()
+                .start
+                .line,
+            3
+        );
+        assert_eq!(front_matter.key_position("owner", content), None);
+
+        assert_eq!(FrontMatter::from_content("# Guide\n"), Ok(None));
+        assert!(FrontMatter::from_content("+++\ntitle = \n+++\n").is_err());
+    }
+
+    #[test]
+    fn file_config_from_front_matter() {
+        let content = "---\ntitle: Guide\ncheckmark:\n  lint:\n    disable: [MD033]\n  spelling: { words: [kubectl] }\n  linkcheck: false\n---\n\n# Guide\n";
+        let file_config = FileConfig::from_file(&file(content)).unwrap().unwrap();
+        let mut config = Config::default();
+        config.spelling.words_whitelist = vec!["checkmark".to_string()];
+        file_config.apply(&mut config);
+        assert_eq!(config.linter.disabled_rules, vec!["MD033".to_string()]);
+        assert!(!config.linter.skip);
+        assert_eq!(
+            config.spelling.words_whitelist,
+            vec!["checkmark".to_string(), "kubectl".to_string()]
+        );
+        assert!(config.link_checker.skip);
+
+        let content = "---\ncheckmark: { lint: false }\n---\n# Guide\n";
+        let file_config = FileConfig::from_file(&file(content)).unwrap().unwrap();
+        file_config.apply(&mut config);
+        assert!(config.linter.skip);
+    }
+
+    #[test]
+    fn no_file_config() {
+        assert_eq!(FileConfig::from_file(&file("# Guide\n")), Ok(None));
+        assert_eq!(
+            FileConfig::from_file(&file("---\ntitle: Guide\n---\n# Guide\n")),
+            Ok(None)
+        );
+        assert!(FileConfig::from_file(&file("---\ncheckmark: { lnt: false }\n---\n")).is_err());
+    }
+}
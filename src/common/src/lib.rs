@@ -12,6 +12,8 @@
 //! - `content`: The content of the markdown file.
 //! - `issues`: A vector of `CheckIssue` that occurred while checking the file.
 //!
+//! Use `file_type()` to know whether the file is a plain Markdown or MDX.
+//!
 //! ## `IssueCategory`
 //!
 //! Represents the type of issue that occurred while checking the markdown file. It is an enum with the following variants:
@@ -21,6 +23,8 @@
 //! - `LinkChecking`: Issue reaching a link from a file (either unreachable local file or URL).
 //! - `Spelling`: Issue with word spelling.
 //! - `Grammar`: Issue with grammar.
+//! - `FrontMatter`: Front matter doesn't match the schema.
+//! - `Stale`: Document has not been reviewed for too long.
 //!
 //! This crate provides functionality to check a markdown file for these issues and report them for further action.
 //!
@@ -29,6 +33,7 @@
 //! CheckIssue::to_sarif_result() panics if any of the required fields has not been set.
 
 pub mod ast;
+pub mod front_matter;
 pub mod tui;
 
 /// Represents single markdown file under check
@@ -39,8 +44,37 @@ pub struct MarkDownFile {
     pub issues: Vec<CheckIssue>,
 }
 
+impl MarkDownFile {
+    /// Flavor of Markdown this file is written in
+    pub fn file_type(&self) -> FileType {
+        FileType::from_path(&self.path)
+    }
+}
+
+/// Flavor of Markdown, detected by the file extension
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FileType {
+    /// CommonMark with GitHub Flavored Markdown extensions
+    Markdown,
+    /// Markdown with JSX, ESM imports/exports and expressions, see https://mdxjs.com
+    Mdx,
+}
+
+impl FileType {
+    /// Detect file type from the path, anything that is not MDX is Markdown
+    pub fn from_path(path: &str) -> Self {
+        match std::path::Path::new(path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some(extension) if extension.eq_ignore_ascii_case("mdx") => Self::Mdx,
+            _ => Self::Markdown,
+        }
+    }
+}
+
 /// Represents type of issue that occurred while check
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 pub enum IssueCategory {
     /// Issue with how document has been formatted
     Formatting,
@@ -54,10 +88,14 @@ pub enum IssueCategory {
     Grammar,
     /// Documentation review suggestion
     Review,
+    /// Front matter doesn't match the schema
+    FrontMatter,
+    /// Document has not been reviewed for too long
+    Stale,
 }
 
 /// Represent how critical issue is
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 pub enum IssueSeverity {
     /// Highest level, bug
     Bug,
@@ -72,7 +110,7 @@ pub enum IssueSeverity {
 }
 
 /// Represents issue found by checking markdown file
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CheckIssue {
     /// Category of the issue
     pub category: IssueCategory,
@@ -96,6 +134,8 @@ pub struct CheckIssue {
     pub message: String,
     /// Possible fixes
     pub fixes: Vec<String>,
+    /// Text that could replace the issue range(from offset start to offset end) to fix it
+    pub replacements: Vec<String>,
 }
 
 /// Builder for `CheckIssue` struct
@@ -112,6 +152,7 @@ pub struct CheckIssueBuilder {
     pub offset_end: Option<usize>,
     pub message: Option<String>,
     pub fixes: Vec<String>,
+    pub replacements: Vec<String>,
 }
 
 impl CheckIssueBuilder {
@@ -189,6 +230,13 @@ impl CheckIssueBuilder {
         self
     }
 
+    /// Push replacement of the issue range
+    #[inline]
+    pub fn push_replacement(mut self, replacement: &str) -> Self {
+        self.replacements.push(replacement.to_owned());
+        self
+    }
+
     #[inline]
     pub fn build(self) -> CheckIssue {
         CheckIssue {
@@ -203,6 +251,7 @@ impl CheckIssueBuilder {
             offset_end: self.offset_end.expect("Issue offset end has not been set, use set_offset_end() method before building an instance"),
             message: self.message.expect("Message has not been set, use set_message() method before building an instance"),
             fixes: self.fixes,
+            replacements: self.replacements,
         }
     }
 }
@@ -293,6 +342,8 @@ impl CheckIssue {
             IssueCategory::Spelling => "spelling",
             IssueCategory::Grammar => "grammar",
             IssueCategory::Review => "review",
+            IssueCategory::FrontMatter => "front-matter",
+            IssueCategory::Stale => "stale",
         };
         serde_sarif::sarif::ResultBuilder::default()
             .level(severity)
@@ -365,6 +416,59 @@ mod tests {
         assert_eq!(expected_offset, actual_offset);
     }
 
+    #[test]
+    fn file_type_from_path() {
+        assert_eq!(FileType::from_path("docs/intro.mdx"), FileType::Mdx);
+        assert_eq!(FileType::from_path("docs/intro.MDX"), FileType::Mdx);
+        assert_eq!(FileType::from_path("README.md"), FileType::Markdown);
+        assert_eq!(FileType::from_path("notes.markdown"), FileType::Markdown);
+        assert_eq!(FileType::from_path("-"), FileType::Markdown);
+    }
+
+    #[test]
+    fn merge_nested_tables() {
+        let mut base: toml::Table = toml::from_str(
+            "[linter]\nmd033_allowed_html_tags = [\"br\"]\n[style]\nheadings = \"atx\"\nbold = \"asterisk\"",
+        )
+        .unwrap();
+        let overlay: toml::Table = toml::from_str(
+            "[linter]\nmd033_allowed_html_tags = [\"img\"]\n[style]\nbold = \"underscore\"",
+        )
+        .unwrap();
+        merge_tables(&mut base, &overlay);
+        let config = Config::from_tables(&[base]).unwrap();
+        assert!(matches!(config.style.headings, HeadingStyle::Atx));
+        assert!(matches!(config.style.bold, BoldStyle::Underscore));
+        assert_eq!(
+            config.linter.md033_allowed_html_tags,
+            vec!["img".to_string()]
+        );
+    }
+
+    #[test]
+    fn invalid_config_is_reported_with_location() {
+        let path = std::env::temp_dir().join("checkmark_invalid_config.toml");
+        let path = path.to_str().unwrap();
+        std::fs::write(path, "[linter]\n\nallowed_html_tags = [\"br\"]\n").unwrap();
+        let err = Config::read_table(path).unwrap_err();
+        assert!(err.starts_with(&format!("{path}:3:1: unknown field `allowed_html_tags`")));
+
+        std::fs::write(path, "[style]\nheadings = \"bold\"\n").unwrap();
+        let err = Config::read_table(path).unwrap_err();
+        assert!(err.starts_with(&format!("{path}:2:12: unknown variant `bold`")));
+
+        std::fs::write(
+            path,
+            "[[overrides]]\nfiles = [\"*.md\"]\nlinter = { md033 = [] }\n",
+        )
+        .unwrap();
+        let err = Config::read_table(path).unwrap_err();
+        assert!(err.starts_with(&format!("{path}:3:")));
+
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(Config::read_table(path), Ok(None));
+    }
+
     // Check that index consider LF format
     #[test]
     fn offset_by_line_number_lf() {
@@ -382,7 +486,8 @@ pub fn activate_debug_logging() {
 }
 
 /// TOML config for checkmark
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct Config {
     #[serde(default)]
     pub compose: ComposeConfig,
@@ -390,12 +495,17 @@ pub struct Config {
     #[serde(default)]
     pub fmt: FmtConfig,
 
+    #[serde(default)]
+    pub front_matter: FrontMatterConfig,
+
     #[serde(default)]
     pub global: GlobalConfig,
 
     #[serde(default)]
     pub link_checker: LinkCheckerConfig,
 
+    /// Where the config was read from, not read from the config file
+    #[serde(skip)]
     pub location: Option<String>,
 
     #[serde(default)]
@@ -413,44 +523,165 @@ pub struct Config {
     #[serde(default)]
     pub spelling: SpellingConfig,
 
+    #[serde(default)]
+    pub stale: StaleConfig,
+
     #[serde(default)]
     pub style: StyleConfig,
+
+    /// Configs this one is based on, they are merged beneath it while reading config files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<String>,
+
+    /// Sections that apply to matching files only, they are applied while building per-file configs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<OverrideConfig>,
+}
+
+/// `[[overrides]]` item: any section of the config that applies to files matching the patterns only
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct OverrideConfig {
+    pub files: Vec<String>,
+
+    pub compose: Option<ComposeConfig>,
+
+    pub fmt: Option<FmtConfig>,
+
+    pub front_matter: Option<FrontMatterConfig>,
+
+    pub global: Option<GlobalConfig>,
+
+    pub link_checker: Option<LinkCheckerConfig>,
+
+    pub linter: Option<LinterConfig>,
+
+    pub open_ai: Option<OpenAiConfig>,
+
+    pub review: Option<ReviewConfig>,
+
+    pub rendering: Option<RenderingConfig>,
+
+    pub spelling: Option<SpellingConfig>,
+
+    pub stale: Option<StaleConfig>,
+
+    pub style: Option<StyleConfig>,
 }
 
+/// Names of config files looked up in a dir, in order of preference
+pub const CONFIG_FILE_NAMES: [&str; 2] = ["checkmark.toml", ".checkmark.toml"];
+
 impl Config {
-    /// Try to build config from TOML file
-    pub fn from_file(path: &str) -> Option<Self> {
-        log::debug!("Trying to build config from file: {}", &path);
-        if let Ok(file) = std::fs::read_to_string(path) {
-            match toml::from_str::<Self>(&file) {
-                Ok(cfg) => {
-                    log::debug!("Config file found in {}: {:#?}", &path, &cfg);
-                    if cfg.open_ai.api_key.is_some() {
-                        log::warn!("OpenAI API key was set in a config file. Please use it only for testing purpose and never commit this file. Use either OPEN_AI_API_KEY environment variable or put it into the \".env\" file instead.");
-                    }
-                    Some(cfg)
-                }
-                Err(err) => {
-                    log::error!("Error while parsing config file: {}", err);
-                    None
-                }
+    /// Try to build config from TOML file. None when there is no such file
+    pub fn from_file(path: &str) -> Result<Option<Self>, String> {
+        match Self::read_table(path)? {
+            Some(table) => Self::from_tables(&[table]).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Try to read TOML file as a plain table, so it could be merged with other ones. None when there is no such file.
+    /// File is validated strictly: unknown keys and values of a wrong type are errors
+    /// pointing to their location, i.e. "checkmark.toml:3:1: unknown field `foo`"
+    pub fn read_table(path: &str) -> Result<Option<toml::Table>, String> {
+        log::debug!("Trying to read config table from file: {}", &path);
+        let Ok(file) = std::fs::read_to_string(path) else {
+            return Ok(None);
+        };
+        let error = |err: toml::de::Error| match err.span() {
+            Some(span) => {
+                let (line, column) = line_column(&file, span.start);
+                format!("{}:{}:{}: {}", path, line, column, err.message().trim())
+            }
+            None => format!("{}: {}", path, err.message().trim()),
+        };
+        let table = toml::from_str::<toml::Table>(&file).map_err(error)?;
+        toml::from_str::<Self>(&file).map_err(error)?;
+        log::debug!("Config file found in {}: {:#?}", &path, &table);
+        Ok(Some(table))
+    }
+
+    /// Try to build config from TOML tables. Every next table overrides values of the previous ones
+    pub fn from_tables(tables: &[toml::Table]) -> Result<Self, String> {
+        let mut merged = toml::Table::new();
+        for table in tables {
+            merge_tables(&mut merged, table);
+        }
+        let cfg = merged
+            .try_into::<Self>()
+            .map_err(|err| err.message().trim().to_string())?;
+        if cfg.open_ai.api_key.is_some() {
+            log::warn!("OpenAI API key was set in a config file. Please use it only for testing purpose and never commit this file. Use either OPEN_AI_API_KEY environment variable or put it into the \".env\" file instead.");
+        }
+        Ok(cfg)
+    }
+
+    /// JSON Schema of the config file, i.e. for completion and validation in editors
+    pub fn schema() -> schemars::Schema {
+        schemars::schema_for!(Self)
+    }
+}
+
+/// 1-based line and column(in characters) of the byte offset in the text
+fn line_column(text: &str, offset: usize) -> (usize, usize) {
+    let before = &text[..offset.min(text.len())];
+    let line_start = before.rfind('\n').map(|index| index + 1).unwrap_or(0);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Deep merge of TOML tables: nested tables are merged key by key,
+/// any other value(including arrays) of the overlay replaces the base one
+pub fn merge_tables(base: &mut toml::Table, overlay: &toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table)
+            }
+            _ => {
+                base.insert(key.clone(), value.clone());
             }
-        } else {
-            None
         }
     }
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct GlobalConfig {
+    #[serde(default = "get_default_include")]
+    pub include: Vec<String>,
+
     #[serde(default)]
     pub exclude: Vec<String>,
 
     #[serde(default)]
     pub exclude_license: bool,
+
+    #[serde(default)]
+    pub no_ignore: bool,
+
+    /// Path reported for the document read from stdin, not read from the config file
+    #[serde(skip)]
+    pub stdin_filename: Option<String>,
+}
+
+impl Default for GlobalConfig {
+    fn default() -> Self {
+        Self {
+            include: get_default_include(),
+            exclude: vec![],
+            exclude_license: false,
+            no_ignore: false,
+            stdin_filename: None,
+        }
+    }
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct FmtConfig {
     #[serde(default)]
     pub check: bool,
@@ -459,7 +690,18 @@ pub struct FmtConfig {
     pub show_diff: bool,
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FrontMatterConfig {
+    /// Path to the JSON Schema that front matter of documents must match
+    #[serde(default)]
+    pub schema: Option<String>,
+
+    #[serde(default)]
+    pub skip: bool,
+}
+
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum HeadingStyle {
     #[default]
@@ -468,7 +710,7 @@ pub enum HeadingStyle {
     Setext,
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum UnorderedListStyle {
     #[default]
@@ -478,7 +720,7 @@ pub enum UnorderedListStyle {
     Plus,
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum BoldStyle {
     #[default]
@@ -487,7 +729,17 @@ pub enum BoldStyle {
     Underscore,
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HardBreakStyle {
+    #[default]
+    Consistent,
+    Backslash,
+    Spaces,
+}
+
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct StyleConfig {
     #[serde(default)]
     pub headings: HeadingStyle,
@@ -503,9 +755,13 @@ pub struct StyleConfig {
 
     #[serde(default)]
     pub default_code_block_language: Option<String>,
+
+    #[serde(default)]
+    pub hard_break: HardBreakStyle,
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct ReviewConfig {
     #[serde(default)]
     pub no_suggestions: bool,
@@ -517,7 +773,8 @@ pub struct ReviewConfig {
     pub creativity: Option<u8>,
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct RenderingConfig {
     #[serde(default)]
     pub output: Option<String>,
@@ -529,12 +786,24 @@ pub struct RenderingConfig {
     pub serve: bool,
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct ComposeConfig {
     #[serde(default)]
     pub creativity: Option<u8>,
 }
 
+fn get_default_include() -> Vec<String> {
+    ["md", "markdown", "mdown", "mdwn", "mkd", "mkdn", "mdx"]
+        .iter()
+        .map(|extension| format!("**/*.{extension}"))
+        .collect()
+}
+
+fn get_default_max_age_days() -> u32 {
+    365
+}
+
 fn get_default_check_emails() -> bool {
     true
 }
@@ -543,7 +812,8 @@ fn get_default_md031_list_items() -> bool {
     true
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct LinkCheckerConfig {
     #[serde(default)]
     pub ignore_wildcards: Vec<String>,
@@ -568,24 +838,59 @@ pub struct LinkCheckerConfig {
 
     #[serde(default)]
     pub allow_insecure: bool,
+
+    #[serde(default)]
+    pub skip: bool,
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct LinterConfig {
     #[serde(default = "get_default_md031_list_items")]
     pub md031_list_items: bool,
 
     #[serde(default)]
     pub md033_allowed_html_tags: Vec<String>,
+
+    #[serde(default)]
+    pub disabled_rules: Vec<String>,
+
+    #[serde(default)]
+    pub skip: bool,
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct SpellingConfig {
     #[serde(default)]
     pub words_whitelist: Vec<String>,
+
+    #[serde(default)]
+    pub skip: bool,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StaleConfig {
+    /// Documents reviewed(or, without a review date, changed) longer ago are stale
+    #[serde(default = "get_default_max_age_days")]
+    pub max_age_days: u32,
+
+    #[serde(default)]
+    pub skip: bool,
+}
+
+impl Default for StaleConfig {
+    fn default() -> Self {
+        Self {
+            max_age_days: get_default_max_age_days(),
+            skip: false,
+        }
+    }
 }
 
-#[derive(Debug, Default, Clone, serde::Deserialize)]
+#[derive(Debug, Default, Clone, serde::Serialize, serde::Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
 pub struct OpenAiConfig {
     #[serde(default)]
     pub api_key: Option<String>,
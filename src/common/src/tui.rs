@@ -8,6 +8,8 @@ use std::sync::{Arc, Mutex};
 /// When dropped - stops spinner
 pub struct CheckProgressTUI {
     ci_mode: bool,
+    muted: bool,
+    root: Option<(std::path::PathBuf, String)>,
     had_any_issue: bool,
     spinner: Option<Spinner>,
     custom_finish_message: Option<String>,
@@ -23,6 +25,8 @@ impl CheckProgressTUI {
     pub fn new(ci_mode: bool) -> Self {
         Self {
             ci_mode,
+            muted: false,
+            root: None,
             had_any_issue: false,
             spinner: None,
             custom_finish_message: None,
@@ -37,7 +41,36 @@ impl CheckProgressTUI {
         Arc::new(Mutex::new(Self::new(ci_mode)))
     }
 
+    /// Stop printing progress to stdout, i.e. when stdout is used for the formatted document.
+    /// Report is printed to stderr, so it is kept
+    pub fn mute(&mut self) {
+        if let Some(spinner) = &mut self.spinner {
+            spinner.stop();
+        }
+        self.spinner = None;
+        self.muted = true;
+    }
+
+    /// Report file paths relative to the dir(i.e. to the checked out remote repo) with a prefix
+    pub fn set_root(&mut self, root: &std::path::Path, prefix: &str) {
+        self.root = Some((root.to_path_buf(), prefix.to_string()));
+    }
+
+    /// Path of the file as it shall be reported
+    pub fn display_path(&self, path: &str) -> String {
+        match &self.root {
+            Some((root, prefix)) => match std::path::Path::new(path).strip_prefix(root) {
+                Ok(relative_path) => format!("{}{}", prefix, relative_path.display()),
+                Err(_) => path.to_string(),
+            },
+            None => path.to_string(),
+        }
+    }
+
     pub fn start_spinner(&mut self, title: &str) {
+        if self.muted {
+            return;
+        }
         if let Some(spinner) = &mut self.spinner {
             spinner.stop_with_newline();
         }
@@ -45,6 +78,9 @@ impl CheckProgressTUI {
     }
 
     pub fn finish_spinner(&mut self) {
+        if self.muted {
+            return;
+        }
         let message = if self.custom_finish_message.is_some() {
             self.custom_finish_message.take().unwrap()
         } else if self.had_any_issue {
@@ -67,15 +103,34 @@ impl CheckProgressTUI {
         }
     }
 
+    /// Clear the terminal before the next check, i.e. in watch mode.
+    /// Does nothing in CI mode to keep the full log
+    pub fn clear_screen(&self) {
+        if !self.ci_mode && !self.muted {
+            print!("\x1B[2J\x1B[1;1H");
+        }
+    }
+
     pub fn print_file_check_status(&mut self, file: &MarkDownFile) {
         self.had_any_issue = file
             .issues
             .iter()
             .any(|issue| issue.severity != IssueSeverity::Help);
         let mut message = match self.had_any_issue {
-            true => format!("{}: {}", "✗ Has issues".red().bold(), file.path),
-            false => format!("{}: {}", "✓ Ok".green().bold(), file.path),
+            true => format!(
+                "{}: {}",
+                "✗ Has issues".red().bold(),
+                self.display_path(&file.path)
+            ),
+            false => format!(
+                "{}: {}",
+                "✓ Ok".green().bold(),
+                self.display_path(&file.path)
+            ),
         };
+        if self.muted {
+            return;
+        }
         if self.spinner.is_some() {
             message = format!("\r{}", &message);
         }
@@ -89,7 +144,10 @@ impl CheckProgressTUI {
 
         let mut codespan_files = SimpleFiles::new();
         for analyzed_file in files {
-            let codespan_file_id = codespan_files.add(&analyzed_file.path, &analyzed_file.content);
+            let codespan_file_id = codespan_files.add(
+                self.display_path(&analyzed_file.path),
+                &analyzed_file.content,
+            );
             for issue in &analyzed_file.issues {
                 let issue_code = match &issue.category {
                     IssueCategory::Formatting => "Formatting",
@@ -98,6 +156,8 @@ impl CheckProgressTUI {
                     IssueCategory::Spelling => "Spelling",
                     IssueCategory::Grammar => "Grammar",
                     IssueCategory::Review => "Review",
+                    IssueCategory::FrontMatter => "FrontMatter",
+                    IssueCategory::Stale => "Stale",
                 };
                 let severity = match &issue.severity {
                     IssueSeverity::Bug => Severity::Bug,